@@ -1,9 +1,56 @@
-use clap::{Parser, Subcommand};
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use anyhow::Result;
+use clap::builder::FalseyValueParser;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, Command, CommandFactory, Parser, Subcommand};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
+
+use crate::clients::{cached_library_ids, read_history};
+use crate::core::completion::BIN_NAME;
+use crate::core::config::{
+    ConfigFile, Overrides, Setting, SettingSource, Settings, default_history_path,
+};
+use crate::core::history::recent_library_ids;
 
 #[derive(Parser)]
 #[command(name = "context7-cli")]
 #[command(about = "CLI for Context7 API", long_about = None)]
 pub struct Cli {
+    /// Config file to read instead of $XDG_CONFIG_HOME/context7-cli/config.toml
+    #[arg(long, global = true, env = "CONTEXT7_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Named profile from the config file
+    #[arg(long, global = true, env = "CONTEXT7_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Context7 API base URL
+    #[arg(long, global = true, env = "CONTEXT7_BASE_URL")]
+    pub base_url: Option<String>,
+
+    /// API key sent as a bearer token
+    #[arg(long, global = true, env = "CONTEXT7_API_KEY", hide_env_values = true)]
+    pub api_key: Option<String>,
+
+    /// Request timeout in seconds (0 disables the timeout)
+    #[arg(long, global = true, env = "CONTEXT7_TIMEOUT")]
+    pub timeout: Option<u64>,
+
+    /// Directory for caching search results and documentation; nothing is cached unless set
+    #[arg(long, global = true, env = "CONTEXT7_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Serve only cached results and documentation, without network access
+    #[arg(long, global = true, env = "CONTEXT7_OFFLINE", value_parser = FalseyValueParser::new())]
+    pub offline: bool,
+
+    /// When to style documentation: auto, always, or never [default: auto]
+    #[arg(long, global = true, env = "CONTEXT7_COLOR")]
+    pub color: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -11,30 +58,470 @@ pub struct Cli {
 #[derive(Subcommand)]
 pub enum Commands {
     /// Search the Context7 API
-    Search {
-        /// Query string to search for
+    Search(Box<SearchArgs>),
+    /// Get documentation for a library by ID
+    GetDocs {
+        /// Library ID (e.g., "/fastapi/fastapi") or alias
+        #[arg(
+            add = ArgValueCandidates::new(library_id_candidates),
+            required_unless_present = "recent"
+        )]
+        id: Option<String>,
+
+        /// Pick from recently fetched libraries instead of giving an ID
+        #[arg(long, conflicts_with = "id")]
+        recent: bool,
+
+        /// Print the markdown as fetched instead of rendering it for the terminal
+        #[arg(long)]
+        raw: bool,
+
+        /// Print a JSON object with the library ID and the markdown as fetched
+        #[arg(long, conflicts_with = "raw")]
+        json: bool,
+    },
+    /// Search and auto-select the most relevant result (like "I'm feeling lucky")
+    Lucky {
+        /// Query string to search for, or an exact library ID (e.g., "/fastapi/fastapi") or alias
         query: String,
 
-        /// Field to sort results by
-        #[arg(long, default_value = "stars")]
-        sort_by: String,
+        /// Relevance weights (e.g., "name=4,trust=1,stars=1,snippets=0.5")
+        #[arg(long)]
+        weights: Option<String>,
 
-        /// Limit the number of results returned
+        /// Print the ranked candidates and their scores to stderr
         #[arg(long)]
-        limit: Option<usize>,
+        explain: bool,
 
-        /// Output only the ID field (one per line)
+        /// Choose among the candidates in a fuzzy finder instead of taking the top result
         #[arg(long)]
-        id_only: bool,
+        pick: bool,
+
+        /// Take the top result even when other candidates score almost as high
+        #[arg(long, conflicts_with = "pick")]
+        force: bool,
+
+        /// Print the markdown as fetched instead of rendering it for the terminal
+        #[arg(long)]
+        raw: bool,
     },
-    /// Get documentation for a library by ID
-    GetDocs {
-        /// Library ID (e.g., "/fastapi/fastapi")
-        id: String,
+    /// Resolve a GitHub URL or package name (e.g., "npm:react") to a Context7 library ID
+    Resolve {
+        /// GitHub or Context7 URL, library ID, alias, or package name with optional npm:/crates:/pypi: prefix
+        input: String,
+
+        /// Output format: text or json
+        #[arg(long, env = "CONTEXT7_FORMAT")]
+        format: Option<String>,
     },
-    /// Search and auto-select the top result (like "I'm feeling lucky")
-    Lucky {
-        /// Query string to search for
+    /// Print the JSON Schema for a machine-readable output (output narrowed with --fields isn't covered)
+    Schema {
+        /// Output to describe: search, search-result, json-envelope, resolve, or docs
+        target: String,
+
+        /// Describe output narrowed with --fields, where every property is optional
+        #[arg(long)]
+        fields: bool,
+    },
+    /// Start an interactive shell for exploring libraries
+    Shell {
+        /// Initial sort order for listed results (see search --sort-by)
+        #[arg(long, allow_hyphen_values = true)]
+        sort_by: Option<String>,
+
+        /// Initial number of results to list
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Browse search results and their docs in a full-screen interface
+    Tui {
+        /// Library to search for, e.g. "react"
         query: String,
     },
+    /// Define short names for library IDs, e.g. "next" for "/vercel/next.js"
+    Alias {
+        #[command(subcommand)]
+        action: AliasAction,
+    },
+    /// List, search or clear the history of searches and fetched libraries
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+    /// Print a shell completion script (bash, zsh, fish, or powershell)
+    Completions {
+        /// Shell to generate the script for
+        shell: String,
+
+        /// Generate a standalone script that doesn't complete library IDs
+        #[arg(long = "static")]
+        static_script: bool,
+    },
+    /// Print a man page in roff, or write all of them to a directory
+    Man {
+        /// Subcommand whose page to print (e.g. "search" or "config show"); the top-level page if omitted
+        command: Vec<String>,
+
+        /// Write the pages for every command into this directory instead
+        #[arg(long, conflicts_with = "command")]
+        out_dir: Option<PathBuf>,
+    },
+    /// Inspect the configuration file and effective settings
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+/// Arguments of the search command
+#[derive(Args)]
+pub struct SearchArgs {
+    /// Query string to search for
+    pub query: String,
+
+    /// Comma-separated fields to sort by; prefix with "+" for ascending or "-" for descending (e.g., "trustScore,-stars"), or "relevance" [default: stars]
+    #[arg(long, allow_hyphen_values = true, env = "CONTEXT7_SORT_BY")]
+    pub sort_by: Option<String>,
+
+    /// Where to place results missing a sort value: first or last [default: last]
+    #[arg(long)]
+    pub missing: Option<String>,
+
+    /// Compare title and id sort keys case-insensitively
+    #[arg(long)]
+    pub ignore_case: bool,
+
+    /// Relevance weights used with --sort-by relevance (e.g., "name=4,trust=1,stars=1,snippets=0.5")
+    #[arg(long)]
+    pub weights: Option<String>,
+
+    /// Limit the number of results returned
+    #[arg(long, env = "CONTEXT7_LIMIT")]
+    pub limit: Option<usize>,
+
+    /// Output only the ID field (one per line)
+    #[arg(long, conflicts_with_all = ["format", "fields", "template", "template_file"])]
+    pub id_only: bool,
+
+    /// Output format: json, ids, table, csv, tsv, ndjson, or json-envelope (defaults to table on a terminal, json otherwise)
+    #[arg(long, env = "CONTEXT7_FORMAT")]
+    pub format: Option<String>,
+
+    /// Comma-separated fields to include in the output (e.g., "id,title,stars,trustScore")
+    #[arg(long, conflicts_with_all = ["template", "template_file"])]
+    pub fields: Option<String>,
+
+    /// Render each result with a template (e.g., "{{id}} ({{stars}}★): {{description}}")
+    #[arg(long, conflicts_with_all = ["format", "template_file"])]
+    pub template: Option<String>,
+
+    /// Read the output template from a file
+    #[arg(long, conflicts_with = "format")]
+    pub template_file: Option<PathBuf>,
+
+    /// Only keep results with at least this many stars
+    #[arg(long)]
+    pub min_stars: Option<i64>,
+
+    /// Only keep results with at least this trust score
+    #[arg(long)]
+    pub min_trust: Option<f64>,
+
+    /// Only keep results with at least this many code snippets
+    #[arg(long)]
+    pub min_snippets: Option<i64>,
+
+    /// Only keep results in this state (e.g., "finalized")
+    #[arg(long)]
+    pub state: Option<String>,
+
+    /// Only keep results updated on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    pub updated_since: Option<String>,
+
+    /// Choose a result in a fuzzy finder and print its documentation
+    #[arg(long, conflicts_with_all = ["id_only", "format", "fields", "template", "template_file"])]
+    pub interactive: bool,
+
+    /// Fetch this page of results (starting at 1)
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub page: Option<u32>,
+
+    /// Fetch every page of results
+    #[arg(long, conflicts_with = "page")]
+    pub all: bool,
+}
+
+#[derive(Subcommand)]
+pub enum AliasAction {
+    /// Save an alias in the config file, replacing any with the same name
+    Add {
+        /// Short name (letters, digits, '.', '-' and '_')
+        name: String,
+        /// Library ID or Context7/GitHub URL the alias stands for
+        id: String,
+    },
+    /// Delete an alias from the config file
+    Remove {
+        /// Alias to delete
+        name: String,
+    },
+    /// Print every alias and its library ID
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum HistoryAction {
+    /// Print the most recent entries, oldest first
+    List {
+        /// Number of entries to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Print entries whose query, library ID or command contains a term
+    Search {
+        /// Text to look for, ignoring case
+        term: String,
+    },
+    /// Delete the history file
+    Clear,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the effective settings and where each one came from
+    Show,
+    /// Print the path of the config file
+    Path,
+}
+
+/// Collect the settings given explicitly by flags or environment variables.
+///
+/// # Arguments
+/// * `matches` - Matches from parsing the command line
+///
+/// # Returns
+/// * Explicit values, each tagged with the flag or variable that set it
+/// * `Err` - If an environment variable clap didn't parse holds an invalid value
+pub fn overrides(matches: &ArgMatches) -> Result<Overrides> {
+    Ok(Overrides {
+        profile: explicit_setting(matches, "profile")?,
+        base_url: explicit_setting(matches, "base_url")?,
+        api_key: explicit_setting(matches, "api_key")?,
+        timeout: explicit_setting(matches, "timeout")?,
+        format: explicit_setting(matches, "format")?,
+        cache_dir: explicit_setting(matches, "cache_dir")?,
+        offline: explicit_setting(matches, "offline")?,
+        color: explicit_setting(matches, "color")?,
+        sort_by: explicit_setting(matches, "sort_by")?,
+        limit: explicit_setting(matches, "limit")?,
+    })
+}
+
+/// Library IDs offered when completing `get-docs`: aliases, then recently
+/// fetched IDs, then any others with cached documentation.
+fn library_id_candidates() -> Vec<CompletionCandidate> {
+    let (config, settings) = completion_config().unzip();
+    let mut candidates: Vec<(String, String)> = config
+        .map(|config| config.aliases)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, id)| (name, format!("alias for {}", id)))
+        .collect();
+
+    let mut add_id = |id: String, help: &str| {
+        if !candidates.iter().any(|(existing, _)| *existing == id) {
+            candidates.push((id, help.to_string()));
+        }
+    };
+
+    let entries = default_history_path()
+        .and_then(|path| read_history(&path).ok())
+        .unwrap_or_default();
+    for entry in recent_library_ids(&entries) {
+        if let Some(id) = &entry.id {
+            add_id(id.clone(), "recent");
+        }
+    }
+
+    if let Some(cache_dir) = settings.and_then(|settings| settings.cache_dir.value) {
+        for id in cached_library_ids(&cache_dir) {
+            add_id(id.to_string(), "cached");
+        }
+    }
+
+    candidates
+        .into_iter()
+        .map(|(value, help)| CompletionCandidate::new(value).help(Some(help.into())))
+        .collect()
+}
+
+/// The config file and settings given by environment variables and the config file.
+///
+/// The line being completed isn't parsed, so its flags are not considered.
+fn completion_config() -> Option<(ConfigFile, Settings)> {
+    let matches = Cli::command()
+        .try_get_matches_from([BIN_NAME, "config", "path"])
+        .ok()?;
+    let config =
+        ConfigFile::load(matches.get_one::<PathBuf>("config").map(PathBuf::as_path)).ok()?;
+    let settings = Settings::resolve(overrides(&matches).ok()?, &config).ok()?;
+    Some((config, settings))
+}
+
+/// Read a setting if it was given on the command line or through its environment variable.
+fn explicit_setting<T>(matches: &ArgMatches, id: &str) -> Result<Option<Setting<T>>>
+where
+    T: FromStr + Clone + Send + Sync + 'static,
+    T::Err: Display,
+{
+    let env = arg_env(&Cli::command(), id);
+    let matches = invoked_matches(matches);
+
+    if matches.ids().any(|present| present == id) {
+        let source = match matches.value_source(id) {
+            Some(ValueSource::CommandLine) => SettingSource::Flag,
+            Some(ValueSource::EnvVariable) => SettingSource::Env(env.unwrap_or_default()),
+            _ => return Ok(None),
+        };
+        return Ok(matches
+            .get_one::<T>(id)
+            .map(|value| Setting::new(value.clone(), source)));
+    }
+
+    // Arguments of other subcommands (e.g. search's --limit under `config show`)
+    // aren't parsed, so read their variables directly
+    let Some(name) = env else {
+        return Ok(None);
+    };
+    match std::env::var(&name) {
+        Ok(raw) if !raw.is_empty() => {
+            let value = raw
+                .parse()
+                .map_err(|err| anyhow::anyhow!("Invalid value '{}' for {}: {}", raw, name, err))?;
+            Ok(Some(Setting::new(value, SettingSource::Env(name))))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Matches for the innermost subcommand that was invoked.
+fn invoked_matches(matches: &ArgMatches) -> &ArgMatches {
+    match matches.subcommand() {
+        Some((_, sub_matches)) => invoked_matches(sub_matches),
+        None => matches,
+    }
+}
+
+/// Environment variable declared for an argument anywhere in the command tree.
+fn arg_env(command: &Command, id: &str) -> Option<String> {
+    command
+        .get_arguments()
+        .find(|arg| arg.get_id() == id)
+        .and_then(|arg| arg.get_env())
+        .map(|env| env.to_string_lossy().into_owned())
+        .or_else(|| {
+            command
+                .get_subcommands()
+                .find_map(|subcommand| arg_env(subcommand, id))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        // Catches misspelled argument IDs in conflicts_with and similar attributes
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_sort_by_accepts_leading_hyphen() {
+        let cli = Cli::try_parse_from(["context7-cli", "search", "react", "--sort-by", "-stars"])
+            .unwrap();
+
+        match cli.command {
+            Commands::Search(args) => assert_eq!(args.sort_by.as_deref(), Some("-stars")),
+            _ => panic!("expected search command"),
+        }
+    }
+
+    #[test]
+    fn test_overrides_from_flags() {
+        let matches = Cli::command()
+            .try_get_matches_from([
+                "context7-cli",
+                "--timeout",
+                "5",
+                "search",
+                "react",
+                "--limit",
+                "3",
+            ])
+            .unwrap();
+
+        let overrides = overrides(&matches).unwrap();
+
+        assert_eq!(
+            overrides.timeout,
+            Some(Setting::new(5, SettingSource::Flag))
+        );
+        assert_eq!(overrides.limit, Some(Setting::new(3, SettingSource::Flag)));
+    }
+
+    #[test]
+    fn test_overrides_ignore_unset_arguments() {
+        // Variables aren't set in the test environment, so only flags count
+        let matches = Cli::command()
+            .try_get_matches_from(["context7-cli", "get-docs", "/pallets/flask"])
+            .unwrap();
+
+        let overrides = overrides(&matches).unwrap();
+
+        assert!(overrides.base_url.is_none());
+        assert!(overrides.profile.is_none());
+    }
+
+    #[test]
+    fn test_format_belongs_to_search_and_resolve() {
+        for args in [
+            ["context7-cli", "search", "react", "--format", "csv"],
+            ["context7-cli", "resolve", "react", "--format", "json"],
+        ] {
+            assert!(Cli::try_parse_from(args).is_ok());
+        }
+        assert!(
+            Cli::try_parse_from(["context7-cli", "get-docs", "/a/b", "--format", "json"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_format_conflicts_with_output_options() {
+        let result = Cli::try_parse_from([
+            "context7-cli",
+            "search",
+            "react",
+            "--format",
+            "csv",
+            "--template",
+            "{{id}}",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_arg_env_finds_subcommand_arguments() {
+        let command = Cli::command();
+        assert_eq!(
+            arg_env(&command, "limit").as_deref(),
+            Some("CONTEXT7_LIMIT")
+        );
+        assert_eq!(
+            arg_env(&command, "base_url").as_deref(),
+            Some("CONTEXT7_BASE_URL")
+        );
+        assert_eq!(arg_env(&command, "query"), None);
+    }
 }
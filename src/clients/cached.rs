@@ -0,0 +1,287 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::clients::Context7ClientTrait;
+use crate::models::{LibraryId, SearchResponse};
+
+/// Wraps another client, saving successful responses to a cache directory and
+/// serving from it alone when offline.
+pub struct CachedClient<C> {
+    inner: C,
+    cache_dir: Option<PathBuf>,
+    offline: bool,
+}
+
+impl<C: Context7ClientTrait + Sync> CachedClient<C> {
+    /// Create a caching client.
+    ///
+    /// # Arguments
+    /// * `inner` - Client used for network requests
+    /// * `cache_dir` - Where responses are stored; `None` disables caching
+    /// * `offline` - Serve only cached responses and never call `inner`
+    pub fn new(inner: C, cache_dir: Option<PathBuf>, offline: bool) -> Self {
+        Self {
+            inner,
+            cache_dir,
+            offline,
+        }
+    }
+
+    fn search_path(&self, query: &str, page: u32) -> Option<PathBuf> {
+        let dir = self.cache_dir.as_ref()?;
+        let key = encode_key(&query.trim().to_lowercase());
+        Some(dir.join("search").join(format!("{}-p{}.json", key, page)))
+    }
+
+    fn docs_path(&self, id: &LibraryId) -> Option<PathBuf> {
+        let dir = self.cache_dir.as_ref()?.join("docs").join(id.owner());
+        let file = match id.version() {
+            Some(version) => format!("{}@{}.txt", id.repo(), version),
+            None => format!("{}.txt", id.repo()),
+        };
+        Some(dir.join(file))
+    }
+
+    /// Read a cached entry, failing with a hint when offline and it's missing.
+    fn read_offline(&self, path: Option<PathBuf>, what: &str) -> Result<String> {
+        let path = path.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Offline mode needs a cache directory; set one with --cache-dir or CONTEXT7_CACHE_DIR"
+            )
+        })?;
+        std::fs::read_to_string(&path).with_context(|| {
+            format!(
+                "{} is not cached (looked in '{}'); run without --offline to fetch it",
+                what,
+                path.display()
+            )
+        })
+    }
+}
+
+impl<C: Context7ClientTrait + Sync> Context7ClientTrait for CachedClient<C> {
+    async fn search_page(&self, query: &str, page: u32) -> Result<SearchResponse> {
+        let path = self.search_path(query, page);
+        if self.offline {
+            let cached = self.read_offline(path, &format!("Search '{}'", query))?;
+            return Ok(serde_json::from_str(&cached)?);
+        }
+
+        let response = self.inner.search_page(query, page).await?;
+        if let Some(path) = path {
+            write_entry(&path, &serde_json::to_string(&response)?);
+        }
+        Ok(response)
+    }
+
+    async fn get_docs(&self, id: &LibraryId) -> Result<String> {
+        let path = self.docs_path(id);
+        if self.offline {
+            return self.read_offline(path, &format!("Documentation for {}", id));
+        }
+
+        let body = self.inner.get_docs(id).await?;
+        if let Some(path) = path {
+            write_entry(&path, &body);
+        }
+        Ok(body)
+    }
+}
+
+/// List the library IDs whose documentation is in a cache directory.
+///
+/// # Arguments
+/// * `cache_dir` - Directory a `CachedClient` writes to
+///
+/// # Returns
+/// * Cached IDs, sorted; unreadable or unexpected entries are skipped
+pub fn cached_library_ids(cache_dir: &Path) -> Vec<LibraryId> {
+    let Ok(owners) = std::fs::read_dir(cache_dir.join("docs")) else {
+        return Vec::new();
+    };
+
+    let mut ids: Vec<LibraryId> = owners
+        .flatten()
+        .filter_map(|owner| {
+            let owner_name = owner.file_name().into_string().ok()?;
+            let entries = std::fs::read_dir(owner.path()).ok()?;
+            Some(entries.flatten().filter_map(move |entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                let stem = file_name.strip_suffix(".txt")?;
+                let path = match stem.split_once('@') {
+                    Some((repo, version)) => format!("{}/{}/{}", owner_name, repo, version),
+                    None => format!("{}/{}", owner_name, stem),
+                };
+                LibraryId::parse(&path).ok()
+            }))
+        })
+        .flatten()
+        .collect();
+
+    ids.sort_by_key(|id| id.to_string());
+    ids
+}
+
+/// Store a cache entry. Failures are ignored: the cache only speeds up
+/// later offline use and must never break an online request.
+fn write_entry(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(path, contents);
+}
+
+/// Turn a query into a file name, percent-encoding anything but ASCII letters, digits, `-` and `_`.
+fn encode_key(key: &str) -> String {
+    key.bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b == b'-' || b == b'_' {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::MockContext7Client;
+
+    fn test_cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "context7-cli-cache-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_encode_key() {
+        assert_eq!(encode_key("react"), "react");
+        assert_eq!(encode_key("next.js app"), "next%2Ejs%20app");
+        assert_eq!(encode_key("../etc"), "%2E%2E%2Fetc");
+    }
+
+    #[tokio::test]
+    async fn test_offline_serves_cached_docs() {
+        let dir = test_cache_dir("docs");
+        let id = LibraryId::parse("/pallets/flask").unwrap();
+
+        let online = CachedClient::new(MockContext7Client::new(), Some(dir.clone()), false);
+        let fetched = online.get_docs(&id).await.unwrap();
+
+        let offline = CachedClient::new(MockContext7Client::new(), Some(dir.clone()), true);
+        assert_eq!(offline.get_docs(&id).await.unwrap(), fetched);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_offline_serves_cached_search() {
+        let dir = test_cache_dir("search");
+
+        let online = CachedClient::new(MockContext7Client::new(), Some(dir.clone()), false);
+        let fetched = online.search("React").await.unwrap();
+
+        // Queries are cached case-insensitively
+        let offline = CachedClient::new(MockContext7Client::new(), Some(dir.clone()), true);
+        let cached = offline.search("react").await.unwrap();
+        assert_eq!(cached.results.len(), fetched.results.len());
+        assert_eq!(cached.page_info, fetched.page_info);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_offline_miss_fails_with_hint() {
+        let dir = test_cache_dir("miss");
+        let offline = CachedClient::new(MockContext7Client::new(), Some(dir), true);
+
+        let error = offline
+            .get_docs(&LibraryId::parse("/pallets/flask/v3.0").unwrap())
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.contains("Documentation for /pallets/flask/v3.0 is not cached"));
+        assert!(error.contains("run without --offline"));
+    }
+
+    #[tokio::test]
+    async fn test_cached_library_ids() {
+        let dir = test_cache_dir("ids");
+        let online = CachedClient::new(MockContext7Client::new(), Some(dir.clone()), false);
+        for id in ["/pallets/flask/v3.0", "/facebook/react", "/pallets/flask"] {
+            online
+                .get_docs(&LibraryId::parse(id).unwrap())
+                .await
+                .unwrap();
+        }
+        write_entry(&dir.join("docs").join("stray.txt"), "not an id");
+
+        let ids: Vec<String> = cached_library_ids(&dir)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert_eq!(
+            ids,
+            vec!["/facebook/react", "/pallets/flask", "/pallets/flask/v3.0"]
+        );
+        assert!(cached_library_ids(&dir.join("missing")).is_empty());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// Client whose requests always fail, like an API answering with an error status
+    struct FailingClient;
+
+    impl Context7ClientTrait for FailingClient {
+        async fn search_page(&self, _query: &str, _page: u32) -> Result<SearchResponse> {
+            anyhow::bail!("HTTP status client error (429 Too Many Requests)")
+        }
+
+        async fn get_docs(&self, _id: &LibraryId) -> Result<String> {
+            anyhow::bail!("HTTP status client error (404 Not Found)")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_request_keeps_cached_entry() {
+        let dir = test_cache_dir("failed");
+        let id = LibraryId::parse("/pallets/flask").unwrap();
+
+        let online = CachedClient::new(MockContext7Client::new(), Some(dir.clone()), false);
+        let fetched = online.get_docs(&id).await.unwrap();
+
+        let failing = CachedClient::new(FailingClient, Some(dir.clone()), false);
+        assert!(failing.get_docs(&id).await.is_err());
+        assert!(failing.search("flask").await.is_err());
+        assert!(!dir.join("search").exists());
+
+        let offline = CachedClient::new(MockContext7Client::new(), Some(dir.clone()), true);
+        assert_eq!(offline.get_docs(&id).await.unwrap(), fetched);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_offline_without_cache_dir_fails() {
+        let offline = CachedClient::new(MockContext7Client::new(), None, true);
+
+        let error = offline.search("react").await.unwrap_err().to_string();
+        assert!(error.contains("--cache-dir"));
+    }
+
+    #[tokio::test]
+    async fn test_online_without_cache_dir_still_works() {
+        let online = CachedClient::new(MockContext7Client::new(), None, false);
+
+        assert!(online.search("react").await.is_ok());
+    }
+}
@@ -1,8 +1,12 @@
+use std::time::Duration;
+
 use anyhow::Result;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 
-use crate::models::SearchResponse;
+use crate::models::{LibraryId, SearchResponse};
 
-const BASE_URL: &str = "https://context7.com/api/v1";
+/// Context7 API endpoint used unless configured otherwise
+pub const DEFAULT_BASE_URL: &str = "https://context7.com/api/v1";
 
 /// Trait defining the interface for Context7 API clients.
 /// This allows us to swap between real and mock implementations for testing.
@@ -11,38 +15,92 @@ pub trait Context7ClientTrait {
     fn search(
         &self,
         query: &str,
+    ) -> impl std::future::Future<Output = Result<SearchResponse>> + Send {
+        self.search_page(query, 1)
+    }
+
+    /// Fetch one page (starting at 1) of libraries matching the query
+    fn search_page(
+        &self,
+        query: &str,
+        page: u32,
     ) -> impl std::future::Future<Output = Result<SearchResponse>> + Send;
 
     /// Get documentation for a specific library ID
-    fn get_docs(&self, id: &str) -> impl std::future::Future<Output = Result<String>> + Send;
+    fn get_docs(&self, id: &LibraryId) -> impl std::future::Future<Output = Result<String>> + Send;
+}
+
+/// Connection settings for the real Context7 client
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    pub base_url: String,
+    pub timeout: Option<Duration>,
+    pub api_key: Option<String>,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            timeout: None,
+            api_key: None,
+        }
+    }
 }
 
 pub struct Context7Client {
     client: reqwest::Client,
+    base_url: String,
 }
 
 impl Context7Client {
     pub fn new() -> Self {
         Self {
             client: reqwest::Client::new(),
+            base_url: DEFAULT_BASE_URL.to_string(),
         }
     }
+
+    /// Create a client for a specific endpoint, request timeout and API key
+    pub fn with_config(config: ClientConfig) -> Result<Self> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(api_key) = config.api_key {
+            let mut value =
+                HeaderValue::from_str(&format!("Bearer {}", api_key)).map_err(|_| {
+                    anyhow::anyhow!("API key contains characters not allowed in a header")
+                })?;
+            // Keeps the key out of debug output
+            value.set_sensitive(true);
+            builder = builder.default_headers(HeaderMap::from_iter([(AUTHORIZATION, value)]));
+        }
+        Ok(Self {
+            client: builder.build()?,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+        })
+    }
 }
 
 impl Context7ClientTrait for Context7Client {
-    async fn search(&self, query: &str) -> Result<SearchResponse> {
-        let url = format!("{}/search?query={}", BASE_URL, query);
-        let response = self.client.get(&url).send().await?;
+    async fn search_page(&self, query: &str, page: u32) -> Result<SearchResponse> {
+        let url = format!("{}/search", self.base_url);
+        let mut request = self.client.get(&url).query(&[("query", query)]);
+        // Page 1 is the API default, so only ask for later pages explicitly
+        if page > 1 {
+            request = request.query(&[("page", page)]);
+        }
+        let response = request.send().await?.error_for_status()?;
         let search_response = response.json().await?;
         Ok(search_response)
     }
 
-    async fn get_docs(&self, id: &str) -> Result<String> {
-        // Strip leading slash if present for URL construction
-        let id_path = id.strip_prefix('/').unwrap_or(id);
-        let url = format!("{}/{}", BASE_URL, id_path);
+    async fn get_docs(&self, id: &LibraryId) -> Result<String> {
+        // Segments are validated by LibraryId, so they are safe to join into the path
+        let url = format!("{}/{}", self.base_url, id.as_path());
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.client.get(&url).send().await?.error_for_status()?;
         let body = response.text().await?;
         Ok(body)
     }
@@ -0,0 +1,240 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::clients::Context7ClientTrait;
+use crate::models::{HistoryEntry, LibraryId, SearchResponse};
+
+/// Entries kept in the history file; older ones are dropped when it grows past this
+pub const MAX_HISTORY_ENTRIES: usize = 1000;
+
+/// Wraps another client, recording each successful search and docs fetch
+/// in a history file.
+pub struct HistoryClient<C> {
+    inner: C,
+    path: Option<PathBuf>,
+    command: String,
+}
+
+impl<C: Context7ClientTrait + Sync> HistoryClient<C> {
+    /// Create a recording client.
+    ///
+    /// # Arguments
+    /// * `inner` - Client that serves the requests
+    /// * `path` - History file to append to; `None` disables recording
+    /// * `command` - Subcommand recorded with each entry
+    pub fn new(inner: C, path: Option<PathBuf>, command: impl Into<String>) -> Self {
+        Self {
+            inner,
+            path,
+            command: command.into(),
+        }
+    }
+
+    /// Append an entry. Failures are ignored: history is a convenience and
+    /// must never break a lookup that succeeded.
+    fn record(&self, query: Option<&str>, id: Option<&LibraryId>) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let entry = HistoryEntry {
+            timestamp: Utc::now(),
+            command: self.command.clone(),
+            query: query.map(str::to_string),
+            id: id.map(ToString::to_string),
+        };
+        let _ = append_history(path, &entry);
+    }
+}
+
+impl<C: Context7ClientTrait + Sync> Context7ClientTrait for HistoryClient<C> {
+    async fn search_page(&self, query: &str, page: u32) -> Result<SearchResponse> {
+        let response = self.inner.search_page(query, page).await?;
+        // Later pages continue the same lookup
+        if page <= 1 {
+            self.record(Some(query), None);
+        }
+        Ok(response)
+    }
+
+    async fn get_docs(&self, id: &LibraryId) -> Result<String> {
+        let body = self.inner.get_docs(id).await?;
+        self.record(None, Some(id));
+        Ok(body)
+    }
+}
+
+/// Read every entry from a history file, oldest first.
+///
+/// # Arguments
+/// * `path` - History file
+///
+/// # Returns
+/// * Entries in the order they were recorded; empty if the file doesn't exist
+/// * `Err` - If the file exists but can't be read
+pub fn read_history(path: &Path) -> Result<Vec<HistoryEntry>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("Failed to read history file '{}'", path.display()));
+        }
+    };
+
+    // A line cut short by an interrupted write shouldn't hide the rest
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Append an entry to a history file, creating it if needed.
+///
+/// Once the file holds more than `MAX_HISTORY_ENTRIES`, it is rewritten
+/// with only the most recent ones.
+pub fn append_history(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open history file '{}'", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    drop(file);
+
+    let entries = read_history(path)?;
+    if entries.len() > MAX_HISTORY_ENTRIES {
+        write_history(path, &entries[entries.len() - MAX_HISTORY_ENTRIES..])?;
+    }
+    Ok(())
+}
+
+/// Remove every entry from a history file.
+pub fn clear_history(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => {
+            Err(err).with_context(|| format!("Failed to clear history file '{}'", path.display()))
+        }
+    }
+}
+
+fn write_history(path: &Path, entries: &[HistoryEntry]) -> Result<()> {
+    let mut contents = String::new();
+    for entry in entries {
+        contents.push_str(&serde_json::to_string(entry)?);
+        contents.push('\n');
+    }
+    std::fs::write(path, contents)
+        .with_context(|| format!("Failed to write history file '{}'", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::{CachedClient, MockContext7Client};
+
+    fn test_history_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "context7-cli-history-{}-{}.jsonl",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn create_test_entry(id: &str) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: Utc::now(),
+            command: "get-docs".to_string(),
+            query: None,
+            id: Some(id.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_searches_and_docs() {
+        let path = test_history_path("record");
+        let client = HistoryClient::new(MockContext7Client::new(), Some(path.clone()), "lucky");
+
+        client.search("flask").await.unwrap();
+        client.search_page("flask", 2).await.unwrap();
+        client
+            .get_docs(&LibraryId::parse("/pallets/flask").unwrap())
+            .await
+            .unwrap();
+
+        let entries = read_history(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command, "lucky");
+        assert_eq!(entries[0].query.as_deref(), Some("flask"));
+        assert_eq!(entries[1].id.as_deref(), Some("/pallets/flask"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_lookups_are_not_recorded() {
+        let path = test_history_path("failed");
+        // Offline without a cache, every lookup fails
+        let inner = CachedClient::new(MockContext7Client::new(), None, true);
+        let client = HistoryClient::new(inner, Some(path.clone()), "get-docs");
+
+        assert!(
+            client
+                .get_docs(&LibraryId::parse("/missing/library").unwrap())
+                .await
+                .is_err()
+        );
+
+        assert!(read_history(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_history_skips_malformed_lines() {
+        let path = test_history_path("malformed");
+        append_history(&path, &create_test_entry("/a/one")).unwrap();
+        std::fs::write(
+            &path,
+            std::fs::read_to_string(&path).unwrap() + "{\"timestamp\":\n",
+        )
+        .unwrap();
+        append_history(&path, &create_test_entry("/a/two")).unwrap();
+
+        let ids: Vec<String> = read_history(&path)
+            .unwrap()
+            .into_iter()
+            .filter_map(|entry| entry.id)
+            .collect();
+        assert_eq!(ids, vec!["/a/one", "/a/two"]);
+
+        clear_history(&path).unwrap();
+        assert!(read_history(&path).unwrap().is_empty());
+        assert!(clear_history(&path).is_ok());
+    }
+
+    #[test]
+    fn test_append_history_keeps_most_recent_entries() {
+        let path = test_history_path("trim");
+        let old: Vec<HistoryEntry> = (0..MAX_HISTORY_ENTRIES)
+            .map(|n| create_test_entry(&format!("/old/lib{}", n)))
+            .collect();
+        write_history(&path, &old).unwrap();
+
+        append_history(&path, &create_test_entry("/new/lib")).unwrap();
+
+        let entries = read_history(&path).unwrap();
+        assert_eq!(entries.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(entries[0].id.as_deref(), Some("/old/lib1"));
+        assert_eq!(entries.last().unwrap().id.as_deref(), Some("/new/lib"));
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
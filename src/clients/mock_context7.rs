@@ -1,8 +1,8 @@
 use anyhow::Result;
 
 use crate::clients::Context7ClientTrait;
-use crate::models::SearchResponse;
 use crate::models::search::SearchResult;
+use crate::models::{LibraryId, PageInfo, SearchResponse};
 
 /// Mock implementation of Context7Client for testing.
 /// Returns predefined test data instead of making real API calls.
@@ -15,7 +15,20 @@ impl MockContext7Client {
 }
 
 impl Context7ClientTrait for MockContext7Client {
-    async fn search(&self, _query: &str) -> Result<SearchResponse> {
+    async fn search_page(&self, _query: &str, page: u32) -> Result<SearchResponse> {
+        // All mock results fit on the first page
+        if page > 1 {
+            return Ok(SearchResponse {
+                results: Vec::new(),
+                page_info: PageInfo {
+                    page: Some(page),
+                    total_pages: Some(1),
+                    total: Some(4),
+                    ..Default::default()
+                },
+            });
+        }
+
         // Return realistic test data with varied characteristics
         let results = vec![
             SearchResult {
@@ -76,10 +89,18 @@ impl Context7ClientTrait for MockContext7Client {
             },
         ];
 
-        Ok(SearchResponse { results })
+        Ok(SearchResponse {
+            page_info: PageInfo {
+                page: Some(1),
+                total_pages: Some(1),
+                total: Some(results.len() as u64),
+                ..Default::default()
+            },
+            results,
+        })
     }
 
-    async fn get_docs(&self, id: &str) -> Result<String> {
+    async fn get_docs(&self, id: &LibraryId) -> Result<String> {
         // Return sample documentation based on the ID
         let docs = format!(
             r#"# Documentation for {}
@@ -1,5 +1,11 @@
+mod cached;
 mod context7;
+mod history;
 mod mock_context7;
 
-pub use context7::{Context7Client, Context7ClientTrait};
+pub use cached::{CachedClient, cached_library_ids};
+pub use context7::{ClientConfig, Context7Client, Context7ClientTrait, DEFAULT_BASE_URL};
+pub use history::{
+    HistoryClient, MAX_HISTORY_ENTRIES, append_history, clear_history, read_history,
+};
 pub use mock_context7::MockContext7Client;
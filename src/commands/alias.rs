@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::core::alias::{format_aliases, remove_alias, set_alias, validate_alias_name};
+use crate::models::LibraryId;
+
+/// Save an alias for a library ID in the config file.
+///
+/// # Arguments
+/// * `config_path` - Config file to update; created if it doesn't exist
+/// * `name` - Short name to define
+/// * `id` - Library ID or supported URL it stands for
+pub fn add(config_path: Option<&Path>, name: &str, id: &str) -> Result<()> {
+    validate_alias_name(name)?;
+    let id = LibraryId::parse(id)?;
+    let path = require_path(config_path)?;
+
+    let updated = set_alias(&read_config(path)?, name, &id)?;
+    write_config(path, &updated)?;
+    eprintln!("Alias '{}' -> {}", name, id);
+    Ok(())
+}
+
+/// Delete an alias from the config file.
+pub fn remove(config_path: Option<&Path>, name: &str) -> Result<()> {
+    let path = require_path(config_path)?;
+
+    let updated = remove_alias(&read_config(path)?, name)?
+        .ok_or_else(|| anyhow::anyhow!("Unknown alias '{}'", name))?;
+    write_config(path, &updated)?;
+    eprintln!("Removed alias '{}'", name);
+    Ok(())
+}
+
+/// Print every alias and the library ID it stands for.
+pub fn list(aliases: &BTreeMap<String, String>) -> Result<()> {
+    if !aliases.is_empty() {
+        println!("{}", format_aliases(aliases));
+    }
+    Ok(())
+}
+
+fn require_path(config_path: Option<&Path>) -> Result<&Path> {
+    config_path.ok_or_else(|| anyhow::anyhow!("Cannot determine the config directory"))
+}
+
+/// Read the config file, treating a missing one as empty.
+fn read_config(path: &Path) -> Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => {
+            Err(err).with_context(|| format!("Failed to read config file '{}'", path.display()))
+        }
+    }
+}
+
+fn write_config(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+        .with_context(|| format!("Failed to write config file '{}'", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ConfigFile;
+
+    #[test]
+    fn test_add_and_remove_alias() {
+        let path = std::env::temp_dir()
+            .join(format!("context7-cli-alias-{}", std::process::id()))
+            .join("config.toml");
+
+        add(Some(&path), "flask", "https://github.com/pallets/flask").unwrap();
+        let config = ConfigFile::load(Some(&path)).unwrap();
+        assert_eq!(config.aliases["flask"], "/pallets/flask");
+        assert!(list(&config.aliases).is_ok());
+
+        remove(Some(&path), "flask").unwrap();
+        assert!(ConfigFile::load(Some(&path)).unwrap().aliases.is_empty());
+        assert!(remove(Some(&path), "flask").is_err());
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_add_rejects_invalid_input() {
+        let path = std::env::temp_dir().join("context7-cli-alias-never-written.toml");
+
+        assert!(add(Some(&path), "bad/name", "/pallets/flask").is_err());
+        assert!(add(Some(&path), "flask", "flask").is_err());
+        assert!(!path.exists());
+        assert!(add(None, "flask", "/pallets/flask").is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::Result;
+use clap::CommandFactory;
+
+use crate::cli::Cli;
+use crate::core::completion::{BIN_NAME, CompletionShell, write_completion_script};
+
+/// Print a completion script for a shell.
+pub fn execute(shell: String, static_script: bool) -> Result<()> {
+    let shell = CompletionShell::from_str(&shell)?;
+    // The dynamic script calls back into this binary, wherever it's installed
+    let completer = std::env::current_exe()
+        .ok()
+        .and_then(|path| path.to_str().map(str::to_string))
+        .unwrap_or_else(|| BIN_NAME.to_string());
+
+    let mut buf = Vec::new();
+    write_completion_script(
+        shell,
+        static_script,
+        &mut Cli::command(),
+        &completer,
+        &mut buf,
+    )?;
+    print!("{}", String::from_utf8_lossy(&buf));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completions_for_each_shell() {
+        for shell in CompletionShell::ALL {
+            assert!(execute(shell.name().to_string(), false).is_ok());
+            assert!(execute(shell.name().to_string(), true).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_completions_invalid_shell() {
+        assert!(execute("tcsh".to_string(), false).is_err());
+    }
+}
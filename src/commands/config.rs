@@ -0,0 +1,39 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::core::config::Settings;
+use crate::core::formatting::format_settings;
+
+/// Print the effective settings and their sources.
+pub fn show(settings: &Settings, config_path: Option<&Path>) -> Result<()> {
+    println!("{}", format_settings(settings, config_path));
+    Ok(())
+}
+
+/// Print the config file path, whether or not the file exists yet.
+pub fn path(config_path: Option<&Path>) -> Result<()> {
+    let path =
+        config_path.ok_or_else(|| anyhow::anyhow!("Cannot determine the config directory"))?;
+    println!("{}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{ConfigFile, Overrides};
+
+    #[test]
+    fn test_show_default_settings() {
+        let settings = Settings::resolve(Overrides::default(), &ConfigFile::default()).unwrap();
+
+        assert!(show(&settings, None).is_ok());
+    }
+
+    #[test]
+    fn test_path_requires_a_location() {
+        assert!(path(None).is_err());
+        assert!(path(Some(Path::new("/tmp/config.toml"))).is_ok());
+    }
+}
@@ -1,9 +1,142 @@
+use std::path::Path;
+
 use anyhow::Result;
 
-use crate::clients::Context7ClientTrait;
+use crate::clients::{Context7ClientTrait, read_history};
+use crate::core::docs::LibraryDocs;
+use crate::core::history::recent_library_ids;
+use crate::core::markdown::DocsFormat;
+use crate::models::search::SearchResult;
+use crate::models::{HistoryEntry, LibraryId};
+use crate::ui::picker;
+
+/// How get-docs prints the documentation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocsOutput {
+    /// The markdown, raw or rendered
+    Text(DocsFormat),
+    /// A JSON object with the library ID and the raw markdown
+    Json,
+}
 
-pub async fn execute<T: Context7ClientTrait>(client: &T, id: String) -> Result<()> {
+pub async fn execute<T: Context7ClientTrait>(
+    client: &T,
+    id: String,
+    output: DocsOutput,
+) -> Result<()> {
+    let id = LibraryId::parse(&id)?;
     let body = client.get_docs(&id).await?;
-    println!("{}", body);
+    let output = match output {
+        DocsOutput::Text(docs_format) => docs_format.format(&body),
+        DocsOutput::Json => serde_json::to_string_pretty(&LibraryDocs {
+            id: id.to_string(),
+            content: body,
+        })?,
+    };
+    println!("{}", output);
     Ok(())
 }
+
+/// Let the user pick one of the recently fetched library IDs.
+///
+/// # Arguments
+/// * `history_path` - History file to read recent IDs from
+///
+/// # Returns
+/// * The chosen library ID
+/// * `Err` - If there is no history, stdin is not a terminal, or the user cancelled
+pub fn pick_recent(history_path: Option<&Path>) -> Result<String> {
+    picker::ensure_terminal()?;
+    let history_path =
+        history_path.ok_or_else(|| anyhow::anyhow!("Cannot determine the data directory"))?;
+    let entries = read_history(history_path)?;
+    let candidates = recent_candidates(&entries);
+    if candidates.is_empty() {
+        anyhow::bail!("No recently fetched libraries. Fetch one with get-docs <id> first");
+    }
+
+    let index = picker::pick(&candidates)?.ok_or_else(|| anyhow::anyhow!("No library selected"))?;
+    Ok(candidates[index].id.clone())
+}
+
+/// Present recent IDs as picker candidates, titled with when and how they were fetched.
+fn recent_candidates(entries: &[HistoryEntry]) -> Vec<SearchResult> {
+    recent_library_ids(entries)
+        .into_iter()
+        .filter_map(|entry| {
+            Some(SearchResult {
+                id: entry.id.clone()?,
+                title: Some(format!(
+                    "{} via {}",
+                    entry.timestamp.format("%Y-%m-%d %H:%M"),
+                    entry.command
+                )),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::MockContext7Client;
+    use chrono::{TimeZone, Utc};
+
+    #[tokio::test]
+    async fn test_get_docs_accepts_urls() {
+        let mock_client = MockContext7Client::new();
+
+        let result = execute(
+            &mock_client,
+            "https://github.com/pallets/flask".to_string(),
+            DocsOutput::Text(DocsFormat::Raw),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_docs_json() {
+        let mock_client = MockContext7Client::new();
+
+        let result = execute(&mock_client, "/pallets/flask".to_string(), DocsOutput::Json).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_recent_candidates_latest_first() {
+        let entry = |id: &str, hour: u32| HistoryEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 1, 15, hour, 0, 0).unwrap(),
+            command: "get-docs".to_string(),
+            query: None,
+            id: Some(id.to_string()),
+        };
+        let entries = vec![entry("/pallets/flask", 9), entry("/facebook/react", 10)];
+
+        let candidates = recent_candidates(&entries);
+
+        assert_eq!(candidates[0].id, "/facebook/react");
+        assert_eq!(
+            candidates[0].title.as_deref(),
+            Some("2025-01-15 10:00 via get-docs")
+        );
+        assert_eq!(candidates[1].id, "/pallets/flask");
+    }
+
+    #[tokio::test]
+    async fn test_get_docs_rejects_invalid_id() {
+        let mock_client = MockContext7Client::new();
+
+        let result = execute(
+            &mock_client,
+            "../search?query=x".to_string(),
+            DocsOutput::Text(DocsFormat::Raw),
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::clients::{clear_history, read_history};
+use crate::core::history::{format_history_entry, search_history};
+
+/// Print the most recent history entries, oldest first.
+pub fn list(history_path: Option<&Path>, limit: usize) -> Result<()> {
+    let entries = read_history(require_path(history_path)?)?;
+    let skip = entries.len().saturating_sub(limit);
+    for entry in &entries[skip..] {
+        println!("{}", format_history_entry(entry));
+    }
+    Ok(())
+}
+
+/// Print the history entries matching a term.
+pub fn search(history_path: Option<&Path>, term: &str) -> Result<()> {
+    let entries = read_history(require_path(history_path)?)?;
+    for entry in search_history(&entries, term) {
+        println!("{}", format_history_entry(entry));
+    }
+    Ok(())
+}
+
+/// Delete the whole history.
+pub fn clear(history_path: Option<&Path>) -> Result<()> {
+    clear_history(require_path(history_path)?)?;
+    eprintln!("History cleared");
+    Ok(())
+}
+
+fn require_path(history_path: Option<&Path>) -> Result<&Path> {
+    history_path.ok_or_else(|| anyhow::anyhow!("Cannot determine the data directory"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::append_history;
+    use crate::models::HistoryEntry;
+
+    #[test]
+    fn test_history_commands() {
+        let path = std::env::temp_dir().join(format!(
+            "context7-cli-history-command-{}.jsonl",
+            std::process::id()
+        ));
+        let entry = HistoryEntry {
+            timestamp: chrono::Utc::now(),
+            command: "search".to_string(),
+            query: Some("react".to_string()),
+            id: None,
+        };
+        append_history(&path, &entry).unwrap();
+
+        assert!(list(Some(&path), 20).is_ok());
+        assert!(search(Some(&path), "react").is_ok());
+        assert!(clear(Some(&path)).is_ok());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_history_requires_a_location() {
+        assert!(list(None, 20).is_err());
+        assert!(clear(None).is_err());
+    }
+}
@@ -1,20 +1,185 @@
 use anyhow::Result;
 
 use crate::clients::Context7ClientTrait;
-use crate::core::sorting::{SortField, sort_search_results};
-use crate::core::validation::validate_search_results_not_empty;
+use crate::core::formatting::format_explanation;
+use crate::core::markdown::DocsFormat;
+use crate::core::scoring::{ScoreWeights, rank_by_relevance};
+use crate::core::validation::{validate_search_results_not_empty, validate_unambiguous};
+use crate::models::LibraryId;
+use crate::ui::picker;
+
+/// Options controlling how lucky picks its result
+#[derive(Default)]
+pub struct LuckyOptions {
+    pub weights: Option<String>,
+    pub explain: bool,
+    pub pick: bool,
+    pub force: bool,
+    /// How the chosen documentation is printed
+    pub docs_format: DocsFormat,
+}
+
+pub async fn execute<T: Context7ClientTrait>(
+    client: &T,
+    query: String,
+    options: LuckyOptions,
+) -> Result<()> {
+    let weights = match &options.weights {
+        Some(weights) => ScoreWeights::from_str(weights)?,
+        None => ScoreWeights::default(),
+    };
+
+    // An exact ID needs no search, so skip straight to fetching it
+    if let Some(id) = LibraryId::from_query(&query) {
+        let body = client.get_docs(&id).await?;
+        println!("{}", options.docs_format.format(&body));
+        return Ok(());
+    }
+
+    if options.pick {
+        picker::ensure_terminal()?;
+    }
 
-pub async fn execute<T: Context7ClientTrait>(client: &T, query: String) -> Result<()> {
     let search_response = client.search(&query).await?;
 
     validate_search_results_not_empty(&search_response.results, &query)?;
 
-    let sorted_results = sort_search_results(search_response.results, SortField::Stars);
+    let ranked = rank_by_relevance(&query, search_response.results, &weights);
+
+    // Explanations go to stderr so stdout stays pipeable documentation
+    if options.explain {
+        eprintln!("{}", format_explanation(&query, &ranked, &weights));
+    }
 
-    let first_result = &sorted_results[0];
+    let chosen = if options.pick {
+        let candidates: Vec<_> = ranked.into_iter().map(|scored| scored.result).collect();
+        let index =
+            picker::pick(&candidates)?.ok_or_else(|| anyhow::anyhow!("No library selected"))?;
+        LibraryId::parse(&candidates[index].id)?
+    } else {
+        if let Err(err) = validate_unambiguous(&ranked, &query) {
+            if !options.force {
+                return Err(err);
+            }
+            eprintln!("Warning: {}", err);
+        }
+        LibraryId::parse(&ranked[0].result.id)?
+    };
 
-    let body = client.get_docs(&first_result.id).await?;
-    println!("{}", body);
+    let body = client.get_docs(&chosen).await?;
+    println!("{}", options.docs_format.format(&body));
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::MockContext7Client;
+    use crate::core::validation::AmbiguousMatchError;
+
+    #[tokio::test]
+    async fn test_lucky_with_mock_client() {
+        let mock_client = MockContext7Client::new();
+
+        let result = execute(&mock_client, "react".to_string(), LuckyOptions::default()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_lucky_with_custom_weights() {
+        let mock_client = MockContext7Client::new();
+
+        let options = LuckyOptions {
+            weights: Some("name=1,stars=2".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "svelte".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_lucky_with_invalid_weights() {
+        let mock_client = MockContext7Client::new();
+
+        let options = LuckyOptions {
+            weights: Some("popularity=1".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "svelte".to_string(), options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lucky_with_explain() {
+        let mock_client = MockContext7Client::new();
+
+        let options = LuckyOptions {
+            explain: true,
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "vue".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_lucky_exact_id_skips_search() {
+        let mock_client = MockContext7Client::new();
+
+        let result = execute(
+            &mock_client,
+            "/pallets/flask".to_string(),
+            LuckyOptions::default(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_lucky_url_skips_search() {
+        let mock_client = MockContext7Client::new();
+
+        let result = execute(
+            &mock_client,
+            "https://context7.com/pallets/flask".to_string(),
+            LuckyOptions::default(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_lucky_refuses_ambiguous_match() {
+        let mock_client = MockContext7Client::new();
+
+        // No mock result matches the name, so the top scores are close together
+        let result = execute(
+            &mock_client,
+            "javascript".to_string(),
+            LuckyOptions::default(),
+        )
+        .await;
+
+        let error = result.unwrap_err();
+        assert!(error.downcast_ref::<AmbiguousMatchError>().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_lucky_force_accepts_ambiguous_match() {
+        let mock_client = MockContext7Client::new();
+
+        let options = LuckyOptions {
+            force: true,
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "javascript".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::CommandFactory;
+
+use crate::cli::Cli;
+use crate::core::manpage::{ManPage, man_pages};
+
+/// Print a man page, or write every page to a directory.
+///
+/// # Arguments
+/// * `command` - Subcommand path whose page to print (e.g. `["config", "show"]`); empty for the top level
+/// * `out_dir` - Write all pages here instead of printing one
+pub fn execute(command: Vec<String>, out_dir: Option<PathBuf>) -> Result<()> {
+    let pages = man_pages(Cli::command())?;
+
+    if let Some(out_dir) = out_dir {
+        std::fs::create_dir_all(&out_dir)
+            .with_context(|| format!("Failed to create '{}'", out_dir.display()))?;
+        for page in &pages {
+            let path = out_dir.join(&page.file_name);
+            std::fs::write(&path, &page.roff)
+                .with_context(|| format!("Failed to write '{}'", path.display()))?;
+            println!("{}", path.display());
+        }
+        return Ok(());
+    }
+
+    print!("{}", find_page(&pages, &command)?.roff);
+    Ok(())
+}
+
+/// Find the page for a subcommand path.
+fn find_page<'a>(pages: &'a [ManPage], command: &[String]) -> Result<&'a ManPage> {
+    pages
+        .iter()
+        .find(|page| page.path == command)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No man page for '{}'. Valid options are: {}",
+                command.join(" "),
+                pages
+                    .iter()
+                    .skip(1)
+                    .map(|page| page.path.join(" "))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Command;
+
+    fn subcommand_paths(command: &Command, prefix: &[String], paths: &mut Vec<Vec<String>>) {
+        for subcommand in command.get_subcommands() {
+            let mut path = prefix.to_vec();
+            path.push(subcommand.get_name().to_string());
+            subcommand_paths(subcommand, &path, paths);
+            paths.push(path);
+        }
+    }
+
+    #[test]
+    fn test_every_subcommand_has_a_page() {
+        let pages = man_pages(Cli::command()).unwrap();
+        let mut paths = Vec::new();
+        subcommand_paths(&Cli::command(), &[], &mut paths);
+
+        assert!(!paths.is_empty());
+        for path in paths {
+            let page = find_page(&pages, &path).unwrap();
+            assert!(page.roff.contains(".TH"), "{}", path.join(" "));
+        }
+    }
+
+    #[test]
+    fn test_unknown_command_lists_pages() {
+        let pages = man_pages(Cli::command()).unwrap();
+
+        let error_msg = find_page(&pages, &["docs".to_string()])
+            .unwrap_err()
+            .to_string();
+
+        assert!(error_msg.contains("No man page for 'docs'"));
+        assert!(error_msg.contains("get-docs"));
+        assert!(error_msg.contains("config show"));
+    }
+
+    #[test]
+    fn test_write_pages_to_directory() {
+        let dir = std::env::temp_dir().join(format!("context7-cli-man-{}", std::process::id()));
+
+        assert!(execute(Vec::new(), Some(dir.clone())).is_ok());
+        assert!(dir.join("context7-cli.1").exists());
+        assert!(dir.join("context7-cli-get-docs.1").exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,3 +1,12 @@
+pub mod alias;
+pub mod completions;
+pub mod config;
 pub mod get_docs;
+pub mod history;
 pub mod lucky;
+pub mod man;
+pub mod resolve;
+pub mod schema;
 pub mod search;
+pub mod shell;
+pub mod tui;
@@ -0,0 +1,104 @@
+use anyhow::Result;
+
+use crate::clients::Context7ClientTrait;
+use crate::core::resolve::{
+    Resolution, ResolveFormat, format_resolution_text, package_search_term,
+};
+use crate::core::scoring::{ScoreWeights, rank_by_relevance};
+use crate::core::validation::validate_search_results_not_empty;
+
+/// Options controlling how resolve prints its answer
+#[derive(Default)]
+pub struct ResolveOptions {
+    pub format: Option<String>,
+}
+
+pub async fn execute<T: Context7ClientTrait>(
+    client: &T,
+    input: String,
+    options: ResolveOptions,
+) -> Result<()> {
+    let format = match &options.format {
+        Some(format) => ResolveFormat::from_str(format)?,
+        None => ResolveFormat::Text,
+    };
+
+    let resolution = match Resolution::direct(&input) {
+        Some(resolution) => resolution,
+        None => {
+            let term = package_search_term(&input);
+            if term.is_empty() {
+                anyhow::bail!("Nothing to resolve in '{}'", input);
+            }
+
+            let search_response = client.search(&term).await?;
+            validate_search_results_not_empty(&search_response.results, &term)?;
+
+            let weights = ScoreWeights::default();
+            let ranked = rank_by_relevance(&term, search_response.results, &weights);
+            Resolution::from_ranked(&input, &ranked, &weights)
+                .ok_or_else(|| anyhow::anyhow!("No results found for query: '{}'", term))?
+        }
+    };
+
+    let output = match format {
+        ResolveFormat::Text => format_resolution_text(&resolution),
+        ResolveFormat::Json => serde_json::to_string_pretty(&resolution)?,
+    };
+    println!("{}", output);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::MockContext7Client;
+
+    #[tokio::test]
+    async fn test_resolve_github_url() {
+        let mock_client = MockContext7Client::new();
+
+        let result = execute(
+            &mock_client,
+            "https://github.com/pallets/flask".to_string(),
+            ResolveOptions::default(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_package_name_as_json() {
+        let mock_client = MockContext7Client::new();
+
+        let options = ResolveOptions {
+            format: Some("json".to_string()),
+        };
+        let result = execute(&mock_client, "npm:react".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_invalid_format() {
+        let mock_client = MockContext7Client::new();
+
+        let options = ResolveOptions {
+            format: Some("yaml".to_string()),
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_empty_package_name() {
+        let mock_client = MockContext7Client::new();
+
+        let result = execute(&mock_client, "npm:".to_string(), ResolveOptions::default()).await;
+
+        assert!(result.is_err());
+    }
+}
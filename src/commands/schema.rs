@@ -0,0 +1,37 @@
+use anyhow::Result;
+
+use crate::core::schema::{SchemaTarget, fields_schema_for_target, schema_for_target};
+
+pub fn execute(target: String, fields: bool) -> Result<()> {
+    let target = SchemaTarget::from_str(&target)?;
+    let schema = if fields {
+        fields_schema_for_target(target)?
+    } else {
+        schema_for_target(target)
+    };
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_for_each_target() {
+        for target in SchemaTarget::ALL {
+            assert!(execute(target.name().to_string(), false).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_schema_with_fields() {
+        assert!(execute("search".to_string(), true).is_ok());
+        assert!(execute("resolve".to_string(), true).is_err());
+    }
+
+    #[test]
+    fn test_schema_invalid_target() {
+        assert!(execute("envelope".to_string(), false).is_err());
+    }
+}
@@ -1,32 +1,364 @@
-use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{SecondsFormat, Utc};
 
 use crate::clients::Context7ClientTrait;
-use crate::core::formatting;
-use crate::core::sorting::{SortField, apply_limit, sort_search_results};
+use crate::core::alias::alias_labels;
+use crate::core::dates::parse_date_arg;
+use crate::core::fields::parse_field_list;
+use crate::core::filtering::{Filter, apply_filters, describe_filters};
+use crate::core::formatting::{self, DEFAULT_TABLE_WIDTH, OutputFormat, SearchFormat, Template};
+use crate::core::markdown::DocsFormat;
+use crate::core::scoring::{ScoreWeights, rank_by_relevance};
+use crate::core::sorting::{MissingPlacement, SortSpec, apply_limit, sort_search_results_by};
+use crate::models::{ENVELOPE_SCHEMA_VERSION, LibraryId, PageInfo, SearchEnvelope, SearchResponse};
+use crate::ui::picker;
+
+/// Sort key that orders results by relevance to the query instead of a field
+const RELEVANCE_SORT: &str = "relevance";
+
+/// Upper bound on pages fetched by --all, in case the API keeps reporting more
+const MAX_PAGES: u32 = 50;
+
+/// Options controlling how search results are ordered and rendered
+pub struct SearchOptions {
+    pub sort_by: String,
+    pub missing: Option<String>,
+    pub ignore_case: bool,
+    pub weights: Option<String>,
+    pub limit: Option<usize>,
+    pub id_only: bool,
+    pub format: Option<String>,
+    pub fields: Option<String>,
+    pub template: Option<String>,
+    pub template_file: Option<PathBuf>,
+    pub min_stars: Option<i64>,
+    pub min_trust: Option<f64>,
+    pub min_snippets: Option<i64>,
+    pub state: Option<String>,
+    pub updated_since: Option<String>,
+    pub interactive: bool,
+    pub page: Option<u32>,
+    pub all: bool,
+    /// Aliases from the config file, shown next to the results they point at
+    pub aliases: BTreeMap<String, String>,
+    /// How documentation chosen with `interactive` is printed
+    pub docs_format: DocsFormat,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            sort_by: "stars".to_string(),
+            missing: None,
+            ignore_case: false,
+            weights: None,
+            limit: None,
+            id_only: false,
+            format: None,
+            fields: None,
+            template: None,
+            template_file: None,
+            min_stars: None,
+            min_trust: None,
+            min_snippets: None,
+            state: None,
+            updated_since: None,
+            interactive: false,
+            page: None,
+            all: false,
+            aliases: BTreeMap::new(),
+            docs_format: DocsFormat::Raw,
+        }
+    }
+}
 
 pub async fn execute<T: Context7ClientTrait>(
     client: &T,
     query: String,
-    sort_by: String,
-    limit: Option<usize>,
-    id_only: bool,
+    options: SearchOptions,
 ) -> Result<()> {
-    let sort_field = SortField::from_str(&sort_by)?;
+    let order = result_order(&options)?;
+    let filters = build_filters(&options)?;
+    let template = load_template(options.template, options.template_file)?;
+    let output_format = resolve_output_format(options.id_only, options.format.as_deref())?;
+    let fields = match options.fields {
+        Some(fields) if output_format.supports_fields() => parse_field_list(&fields)?,
+        Some(_) => anyhow::bail!("--fields is not supported with the ids format"),
+        None => Vec::new(),
+    };
+
+    if options.interactive {
+        picker::ensure_terminal()?;
+    }
 
-    let search_response = client.search(&query).await?;
+    let search_response = if options.all {
+        fetch_all_pages(client, &query).await?
+    } else {
+        client
+            .search_page(&query, options.page.unwrap_or(1))
+            .await?
+    };
+    let fetched_at = Utc::now();
+    let page_info = search_response.page_info;
+    let fetched = search_response.results.len();
 
-    let mut results = sort_search_results(search_response.results, sort_field);
+    let results = apply_filters(search_response.results, &filters);
+    let mut results = match &order {
+        ResultOrder::Fields(spec) => sort_search_results_by(results, spec),
+        ResultOrder::Relevance(weights) => rank_by_relevance(&query, results, weights)
+            .into_iter()
+            .map(|scored| scored.result)
+            .collect(),
+    };
 
-    if let Some(limit) = limit {
+    if let Some(limit) = options.limit {
         results = apply_limit(results, limit);
     }
 
-    let output = formatting::format_search_results(&results, id_only)?;
+    if options.interactive {
+        let index =
+            picker::pick(&results)?.ok_or_else(|| anyhow::anyhow!("No library selected"))?;
+        let id = LibraryId::parse(&results[index].id)?;
+        let body = client.get_docs(&id).await?;
+        println!("{}", options.docs_format.format(&body));
+        return Ok(());
+    }
+
+    let aliases = alias_labels(&results, &options.aliases);
+    let output = match (template, output_format) {
+        (Some(template), _) => formatting::format_with_template(&results, &template, &aliases)?,
+        (None, SearchFormat::JsonEnvelope) => {
+            let (weights, missing, ignore_case) = match &order {
+                ResultOrder::Relevance(weights) => (Some(weights.describe()), None, None),
+                ResultOrder::Fields(spec) => (
+                    None,
+                    Some(spec.missing.name().to_string()),
+                    Some(spec.ignore_case),
+                ),
+            };
+            let envelope = SearchEnvelope {
+                schema_version: ENVELOPE_SCHEMA_VERSION,
+                query: query.clone(),
+                sort_by: options.sort_by.clone(),
+                weights,
+                missing,
+                ignore_case,
+                filters: describe_filters(&filters),
+                limit: options.limit,
+                page: options.page,
+                fetched_at: fetched_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+                count: results.len(),
+                total: page_info.total,
+                results,
+            };
+            formatting::format_envelope(envelope, &fields, &aliases)?
+        }
+        (None, SearchFormat::Results(format)) => formatting::format_search_results(
+            &results,
+            format,
+            &fields,
+            &aliases,
+            terminal_width(),
+        )?,
+    };
     println!("{}", output);
 
+    // Goes to stderr so it never corrupts piped output
+    if let Some(note) = truncation_note(&page_info, fetched, options.page, options.all) {
+        eprintln!("{}", note);
+    }
+
     Ok(())
 }
 
+/// Fetch every page of results, stopping when the API reports no more pages,
+/// a page comes back empty or only repeats results already seen.
+async fn fetch_all_pages<T: Context7ClientTrait>(
+    client: &T,
+    query: &str,
+) -> Result<SearchResponse> {
+    let mut combined = client.search_page(query, 1).await?;
+    let mut seen: HashSet<String> = combined.results.iter().map(|r| r.id.clone()).collect();
+
+    let mut page = 1;
+    while page < MAX_PAGES && combined.page_info.has_more_after(page) != Some(false) {
+        page += 1;
+        let response = client.search_page(query, page).await?;
+        let new_results: Vec<_> = response
+            .results
+            .into_iter()
+            .filter(|result| seen.insert(result.id.clone()))
+            .collect();
+        if new_results.is_empty() {
+            break;
+        }
+        combined.results.extend(new_results);
+        combined.page_info = response.page_info;
+    }
+
+    combined.page_info.page = Some(page);
+    Ok(combined)
+}
+
+/// Describe how many results exist beyond those fetched, if the API says so.
+///
+/// # Returns
+/// * `Some(note)` - When more pages exist after the one fetched, or `--all`
+///   stopped at `MAX_PAGES` without the API reporting the last page
+/// * `None` - When everything was fetched, the last page was fetched, or the
+///   API gave no total
+fn truncation_note(
+    page_info: &PageInfo,
+    fetched: usize,
+    page: Option<u32>,
+    all: bool,
+) -> Option<String> {
+    if all {
+        let capped =
+            page_info.page == Some(MAX_PAGES) && page_info.has_more_after(MAX_PAGES) != Some(false);
+        if !capped {
+            return None;
+        }
+        return Some(match page_info.total {
+            Some(total) => format!(
+                "Fetched {} of {} results; --all stops after {} pages",
+                fetched, total, MAX_PAGES
+            ),
+            None => format!(
+                "Fetched {} results; --all stops after {} pages and more may exist",
+                fetched, MAX_PAGES
+            ),
+        });
+    }
+
+    let page = page.unwrap_or(1);
+    if page_info.has_more_after(page) == Some(false) {
+        return None;
+    }
+    let total = page_info.total?;
+    if total <= fetched as u64 {
+        return None;
+    }
+
+    let position = match page_info.total_pages {
+        Some(total_pages) => format!("page {} of {}", page, total_pages),
+        None => format!("page {}", page),
+    };
+    Some(format!(
+        "Fetched {} of {} results ({}); use --page {} or --all for more",
+        fetched,
+        total,
+        position,
+        page + 1
+    ))
+}
+
+/// How search results are ordered before limiting
+enum ResultOrder {
+    Fields(SortSpec),
+    Relevance(ScoreWeights),
+}
+
+/// Work out the ordering from `--sort-by` and the options that tune it.
+///
+/// `--weights` only applies to relevance ranking, while `--missing` and
+/// `--ignore-case` only apply to field sorts, so giving one with the other
+/// kind of ordering is an error rather than being silently ignored.
+fn result_order(options: &SearchOptions) -> Result<ResultOrder> {
+    if options.sort_by == RELEVANCE_SORT {
+        if options.missing.is_some() {
+            anyhow::bail!("--missing can't be used with --sort-by relevance");
+        }
+        if options.ignore_case {
+            anyhow::bail!("--ignore-case can't be used with --sort-by relevance");
+        }
+        let weights = match &options.weights {
+            Some(weights) => ScoreWeights::from_str(weights)?,
+            None => ScoreWeights::default(),
+        };
+        return Ok(ResultOrder::Relevance(weights));
+    }
+
+    if options.weights.is_some() {
+        anyhow::bail!("--weights can only be used with --sort-by relevance");
+    }
+    let missing = match &options.missing {
+        Some(missing) => MissingPlacement::from_str(missing)?,
+        None => MissingPlacement::Last,
+    };
+    Ok(ResultOrder::Fields(
+        SortSpec::from_str(&options.sort_by)?
+            .with_missing(missing)
+            .with_ignore_case(options.ignore_case),
+    ))
+}
+
+/// Collect the metric threshold filters requested on the command line.
+fn build_filters(options: &SearchOptions) -> Result<Vec<Filter>> {
+    let mut filters = Vec::new();
+    if let Some(min_stars) = options.min_stars {
+        filters.push(Filter::MinStars(min_stars));
+    }
+    if let Some(min_trust) = options.min_trust {
+        filters.push(Filter::MinTrustScore(min_trust));
+    }
+    if let Some(min_snippets) = options.min_snippets {
+        filters.push(Filter::MinSnippets(min_snippets));
+    }
+    if let Some(state) = &options.state {
+        filters.push(Filter::State(state.clone()));
+    }
+    if let Some(updated_since) = &options.updated_since {
+        filters.push(Filter::UpdatedSince(parse_date_arg(updated_since)?));
+    }
+    Ok(filters)
+}
+
+/// Parse the output template from either the inline flag or a template file.
+fn load_template(
+    template: Option<String>,
+    template_file: Option<PathBuf>,
+) -> Result<Option<Template>> {
+    let source = match (template, template_file) {
+        (Some(template), _) => template,
+        (None, Some(path)) => {
+            let contents = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read template file '{}'", path.display()))?;
+            // Editors usually add a trailing newline; results are already newline-separated
+            contents
+                .strip_suffix('\n')
+                .map(str::to_string)
+                .unwrap_or(contents)
+        }
+        (None, None) => return Ok(None),
+    };
+    Template::parse(&source).map(Some)
+}
+
+/// Pick the output format from the CLI flags, falling back to a table when
+/// stdout is a terminal and JSON when it is piped.
+fn resolve_output_format(id_only: bool, format: Option<&str>) -> Result<SearchFormat> {
+    if id_only {
+        return Ok(SearchFormat::Results(OutputFormat::Ids));
+    }
+    match format {
+        Some(format) => SearchFormat::from_str(format),
+        None if std::io::stdout().is_terminal() => Ok(SearchFormat::Results(OutputFormat::Table)),
+        None => Ok(SearchFormat::Results(OutputFormat::Json)),
+    }
+}
+
+/// Width of the attached terminal, or a sensible default when not a terminal.
+fn terminal_width() -> usize {
+    crossterm::terminal::size()
+        .map(|(columns, _)| columns as usize)
+        .unwrap_or(DEFAULT_TABLE_WIDTH)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -37,14 +369,7 @@ mod tests {
         let mock_client = MockContext7Client::new();
 
         // Test that search executes without errors using mock data
-        let result = execute(
-            &mock_client,
-            "react".to_string(),
-            "stars".to_string(),
-            None,
-            false,
-        )
-        .await;
+        let result = execute(&mock_client, "react".to_string(), SearchOptions::default()).await;
 
         assert!(result.is_ok());
     }
@@ -54,14 +379,25 @@ mod tests {
         let mock_client = MockContext7Client::new();
 
         // Test that search with limit executes successfully
-        let result = execute(
-            &mock_client,
-            "javascript".to_string(),
-            "stars".to_string(),
-            Some(2),
-            false,
-        )
-        .await;
+        let options = SearchOptions {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "javascript".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_table_with_aliases() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            format: Some("table".to_string()),
+            aliases: BTreeMap::from([("react".to_string(), "/facebook/react".to_string())]),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
 
         assert!(result.is_ok());
     }
@@ -71,15 +407,458 @@ mod tests {
         let mock_client = MockContext7Client::new();
 
         // Test that id-only mode works
-        let result = execute(
-            &mock_client,
-            "vue".to_string(),
-            "trustScore".to_string(),
-            None,
-            true,
-        )
-        .await;
+        let options = SearchOptions {
+            sort_by: "trustScore".to_string(),
+            id_only: true,
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "vue".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_table_format() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            format: Some("table".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_invalid_format() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            format: Some("xml".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_csv_with_fields() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            format: Some("csv".to_string()),
+            fields: Some("id,stars".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_json_with_fields() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            format: Some("json".to_string()),
+            fields: Some("id,title,stars,trustScore".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_fields_rejected_for_ids() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            format: Some("ids".to_string()),
+            fields: Some("id".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_invalid_field_name() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            format: Some("json".to_string()),
+            fields: Some("id,popularity".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Invalid field"));
+    }
+
+    #[tokio::test]
+    async fn test_search_with_template() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            template: Some("{{id}} ({{stars}}★): {{description}}".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_with_invalid_template() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            template: Some("{{nope}}".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_multi_key_sort() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            sort_by: "trustScore,+stars".to_string(),
+            missing: Some("first".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "javascript".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_sort_by_title_ignore_case() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            sort_by: "title,-lastUpdateDate".to_string(),
+            ignore_case: true,
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "javascript".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_sort_by_relevance() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            sort_by: "relevance".to_string(),
+            weights: Some("name=5".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "vue".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_rejects_options_for_the_other_ordering() {
+        let mock_client = MockContext7Client::new();
+
+        let cases = [
+            SearchOptions {
+                weights: Some("nmae=3".to_string()),
+                ..Default::default()
+            },
+            SearchOptions {
+                sort_by: "relevance".to_string(),
+                missing: Some("first".to_string()),
+                ..Default::default()
+            },
+            SearchOptions {
+                sort_by: "relevance".to_string(),
+                ignore_case: true,
+                ..Default::default()
+            },
+        ];
+        for options in cases {
+            let error = execute(&mock_client, "react".to_string(), options)
+                .await
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("--sort-by relevance"), "{}", error);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_invalid_missing_placement() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            missing: Some("middle".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "javascript".to_string(), options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_with_filters() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            min_stars: Some(50000),
+            min_trust: Some(9.0),
+            state: Some("active".to_string()),
+            updated_since: Some("2025-01-01".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "javascript".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_with_invalid_updated_since() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            updated_since: Some("last week".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "javascript".to_string(), options).await;
+
+        assert!(result.unwrap_err().to_string().contains("Invalid date"));
+    }
+
+    #[test]
+    fn test_build_filters_from_options() {
+        let options = SearchOptions {
+            min_stars: Some(1000),
+            min_snippets: Some(50),
+            ..Default::default()
+        };
+
+        let filters = build_filters(&options).unwrap();
+
+        assert_eq!(
+            filters,
+            vec![Filter::MinStars(1000), Filter::MinSnippets(50)]
+        );
+    }
+
+    #[test]
+    fn test_load_template_from_file_strips_trailing_newline() {
+        let path =
+            std::env::temp_dir().join(format!("context7-cli-template-{}.txt", std::process::id()));
+        std::fs::write(&path, "{{id}}\n").unwrap();
+
+        let template = load_template(None, Some(path.clone())).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(template, Template::parse("{{id}}").unwrap());
+    }
+
+    #[test]
+    fn test_load_template_missing_file() {
+        let result = load_template(None, Some(PathBuf::from("/nonexistent/template.txt")));
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Failed to read template file")
+        );
+    }
+
+    #[test]
+    fn test_resolve_output_format_id_only_wins() {
+        assert_eq!(
+            resolve_output_format(true, None).unwrap(),
+            SearchFormat::Results(OutputFormat::Ids)
+        );
+    }
+
+    #[test]
+    fn test_resolve_output_format_explicit() {
+        assert_eq!(
+            resolve_output_format(false, Some("table")).unwrap(),
+            SearchFormat::Results(OutputFormat::Table)
+        );
+    }
+
+    /// Serves `pages` pages of two results each, optionally without metadata
+    struct PagedClient {
+        pages: u32,
+        report_metadata: bool,
+    }
+
+    impl Context7ClientTrait for PagedClient {
+        async fn search_page(&self, _query: &str, page: u32) -> Result<SearchResponse> {
+            // Without metadata the fake API ignores the page, like a server without paging
+            let page = if self.report_metadata { page } else { 1 };
+            let results = if page <= self.pages {
+                (0..2)
+                    .map(|i| crate::models::search::SearchResult {
+                        id: format!("/page{}/lib{}", page, i),
+                        ..Default::default()
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            let page_info = if self.report_metadata {
+                PageInfo {
+                    page: Some(page),
+                    total_pages: Some(self.pages),
+                    total: Some(self.pages as u64 * 2),
+                    ..Default::default()
+                }
+            } else {
+                PageInfo::default()
+            };
+            Ok(SearchResponse { results, page_info })
+        }
+
+        async fn get_docs(&self, id: &LibraryId) -> Result<String> {
+            Ok(id.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_json_envelope() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            format: Some("json-envelope".to_string()),
+            fields: Some("id,stars".to_string()),
+            min_stars: Some(1000),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_with_page() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            page: Some(2),
+            format: Some("json".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_search_with_all_pages() {
+        let mock_client = MockContext7Client::new();
+
+        let options = SearchOptions {
+            all: true,
+            format: Some("json".to_string()),
+            ..Default::default()
+        };
+        let result = execute(&mock_client, "react".to_string(), options).await;
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_fetch_all_pages_follows_total_pages() {
+        let client = PagedClient {
+            pages: 3,
+            report_metadata: true,
+        };
+
+        let response = fetch_all_pages(&client, "lib").await.unwrap();
+
+        assert_eq!(response.results.len(), 6);
+        assert_eq!(response.results[5].id, "/page3/lib1");
+        assert_eq!(response.page_info.page, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_pages_stops_on_repeated_results() {
+        let client = PagedClient {
+            pages: 3,
+            report_metadata: false,
+        };
+
+        let response = fetch_all_pages(&client, "lib").await.unwrap();
+
+        assert_eq!(response.results.len(), 2);
+    }
+
+    #[test]
+    fn test_truncation_note_when_more_results_exist() {
+        let page_info = PageInfo {
+            total: Some(25),
+            total_pages: Some(3),
+            ..Default::default()
+        };
+
+        let note = truncation_note(&page_info, 10, None, false).unwrap();
+
+        assert_eq!(
+            note,
+            "Fetched 10 of 25 results (page 1 of 3); use --page 2 or --all for more"
+        );
+    }
+
+    #[test]
+    fn test_truncation_note_silent_when_complete_or_unknown() {
+        let page_info = PageInfo {
+            total: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(truncation_note(&page_info, 4, None, false), None);
+        assert_eq!(truncation_note(&page_info, 2, None, true), None);
+        assert_eq!(truncation_note(&PageInfo::default(), 2, None, false), None);
+    }
+
+    #[test]
+    fn test_truncation_note_silent_on_last_page() {
+        let page_info = PageInfo {
+            page: Some(3),
+            total: Some(25),
+            total_pages: Some(3),
+            ..Default::default()
+        };
+
+        assert_eq!(truncation_note(&page_info, 5, Some(3), false), None);
+    }
+
+    #[test]
+    fn test_truncation_note_when_all_hits_page_cap() {
+        let page_info = PageInfo {
+            page: Some(MAX_PAGES),
+            total: Some(2000),
+            total_pages: Some(200),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            truncation_note(&page_info, 500, None, true).unwrap(),
+            "Fetched 500 of 2000 results; --all stops after 50 pages"
+        );
+
+        let last_page = PageInfo {
+            total_pages: Some(MAX_PAGES),
+            ..page_info
+        };
+        assert_eq!(truncation_note(&last_page, 500, None, true), None);
+    }
 }
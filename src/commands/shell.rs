@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::clients::Context7ClientTrait;
+use crate::commands::lucky::{self, LuckyOptions};
+use crate::core::alias::{alias_labels, expand_alias};
+use crate::core::markdown::DocsFormat;
+use crate::core::repl::{
+    SHELL_HELP, ShellCommand, ShellState, complete_shell_line, format_listing, library_page_url,
+};
+
+/// Prompt shown before each line
+const PROMPT: &str = "context7> ";
+
+/// Options for the interactive shell
+pub struct ShellOptions {
+    pub sort_by: String,
+    pub limit: Option<usize>,
+    pub aliases: BTreeMap<String, String>,
+    /// File the line history is loaded from and saved to; `None` keeps it in memory
+    pub history_path: Option<PathBuf>,
+    /// How documentation printed by `docs` and `lucky` is formatted
+    pub docs_format: DocsFormat,
+}
+
+/// Whether the shell should keep reading lines after a command
+#[derive(Debug, PartialEq)]
+enum Flow {
+    Continue,
+    Exit,
+}
+
+pub async fn execute<T: Context7ClientTrait>(client: &T, options: ShellOptions) -> Result<()> {
+    let mut state = ShellState::new(options.sort_by.clone(), options.limit)?;
+    let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new()?;
+    editor.set_helper(Some(ShellHelper {
+        targets: completion_targets(&state, &options.aliases),
+    }));
+    if let Some(path) = &options.history_path {
+        // A missing file just means this is the first session
+        let _ = editor.load_history(path);
+    }
+
+    eprintln!("Type 'help' for commands, 'exit' or Ctrl-D to leave.");
+    loop {
+        let line = match editor.readline(PROMPT) {
+            Ok(line) => line,
+            // Ctrl-C abandons the current line, like a regular shell
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err.into()),
+        };
+        if !line.trim().is_empty() {
+            editor.add_history_entry(line.as_str())?;
+        }
+
+        let command = match ShellCommand::parse(&line) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                continue;
+            }
+        };
+        if command == ShellCommand::History {
+            for (number, entry) in editor.history().iter().enumerate() {
+                println!("{:>5}  {}", number + 1, entry);
+            }
+            continue;
+        }
+
+        match run_command(client, &mut state, &options, command).await {
+            Ok(Flow::Continue) => {}
+            Ok(Flow::Exit) => break,
+            Err(err) => eprintln!("Error: {}", err),
+        }
+        if let Some(helper) = editor.helper_mut() {
+            helper.targets = completion_targets(&state, &options.aliases);
+        }
+    }
+
+    if let Some(path) = &options.history_path {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = editor.save_history(path);
+    }
+    Ok(())
+}
+
+/// Run one shell command against the client, updating the shell state.
+async fn run_command<T: Context7ClientTrait>(
+    client: &T,
+    state: &mut ShellState,
+    options: &ShellOptions,
+    command: ShellCommand,
+) -> Result<Flow> {
+    let aliases = &options.aliases;
+    match command {
+        ShellCommand::Search(query) => {
+            let results = client.search(&query).await?.results;
+            state.set_results(&query, results);
+            print_listing(state, aliases);
+        }
+        ShellCommand::Docs(target) => {
+            let id = state.target(&target, aliases)?;
+            let body = client.get_docs(&id).await?;
+            println!("{}", options.docs_format.format(&body));
+        }
+        ShellCommand::Lucky(query) => {
+            let query = expand_alias(&query, aliases);
+            let lucky_options = LuckyOptions {
+                docs_format: options.docs_format,
+                ..Default::default()
+            };
+            lucky::execute(client, query, lucky_options).await?;
+        }
+        ShellCommand::Open(target) => {
+            let url = library_page_url(&state.target(&target, aliases)?);
+            open_in_browser(&url)?;
+            eprintln!("Opened {}", url);
+        }
+        ShellCommand::Sort(None) => println!("sort: {}", state.sort_by()),
+        ShellCommand::Sort(Some(sort_by)) => {
+            state.set_sort(&sort_by)?;
+            print_listing(state, aliases);
+        }
+        ShellCommand::Limit(None) => match state.limit() {
+            Some(limit) => println!("limit: {}", limit),
+            None => println!("limit: none"),
+        },
+        ShellCommand::Limit(Some(limit)) => {
+            state.set_limit(&limit)?;
+            print_listing(state, aliases);
+        }
+        ShellCommand::Help => println!("{}", SHELL_HELP),
+        ShellCommand::Exit => return Ok(Flow::Exit),
+        // Needs the line editor, so the read loop answers it
+        ShellCommand::History => {}
+    }
+    Ok(Flow::Continue)
+}
+
+fn print_listing(state: &ShellState, aliases: &BTreeMap<String, String>) {
+    let listed = state.listed();
+    if listed.is_empty() {
+        eprintln!("No results");
+    } else {
+        println!(
+            "{}",
+            format_listing(&listed, &alias_labels(&listed, aliases))
+        );
+    }
+}
+
+/// Aliases and listed IDs offered after `docs` and `open`.
+fn completion_targets(state: &ShellState, aliases: &BTreeMap<String, String>) -> Vec<String> {
+    aliases.keys().cloned().chain(state.listed_ids()).collect()
+}
+
+/// Open a URL with the platform's default handler.
+fn open_in_browser(url: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    let status = command
+        .arg(url)
+        .status()
+        .map_err(|err| anyhow::anyhow!("Failed to open a browser for {}: {}", url, err))?;
+    if !status.success() {
+        anyhow::bail!("Failed to open a browser for {}", url);
+    }
+    Ok(())
+}
+
+/// Tab completion for the shell's line editor
+struct ShellHelper {
+    targets: Vec<String>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, words) = complete_shell_line(&line[..pos], &self.targets);
+        let pairs = words
+            .into_iter()
+            .map(|word| Pair {
+                display: word.clone(),
+                replacement: word,
+            })
+            .collect();
+        Ok((start, pairs))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::MockContext7Client;
+
+    async fn run(state: &mut ShellState, line: &str) -> Result<Flow> {
+        let options = ShellOptions {
+            sort_by: "stars".to_string(),
+            limit: None,
+            aliases: BTreeMap::from([("react".to_string(), "/facebook/react".to_string())]),
+            history_path: None,
+            docs_format: DocsFormat::Raw,
+        };
+        let command = ShellCommand::parse(line)?.unwrap();
+        run_command(&MockContext7Client::new(), state, &options, command).await
+    }
+
+    #[tokio::test]
+    async fn test_search_then_docs_by_position() {
+        let mut state = ShellState::new("stars".to_string(), None).unwrap();
+
+        assert_eq!(
+            run(&mut state, "search react").await.unwrap(),
+            Flow::Continue
+        );
+        assert_eq!(state.listed().len(), 4);
+        assert!(run(&mut state, "docs 2").await.is_ok());
+        assert!(run(&mut state, "docs 9").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_labels_aliased_results() {
+        let mut state = ShellState::new("stars".to_string(), None).unwrap();
+
+        run(&mut state, "search react").await.unwrap();
+
+        let listed = state.listed();
+        let aliases = BTreeMap::from([("react".to_string(), "/facebook/react".to_string())]);
+        let listing = format_listing(&listed, &alias_labels(&listed, &aliases));
+        assert!(listing.contains("/facebook/react  React  ★220000  (react)"));
+    }
+
+    #[tokio::test]
+    async fn test_sort_and_limit_reorder_last_results() {
+        let mut state = ShellState::new("stars".to_string(), None).unwrap();
+        run(&mut state, "search javascript").await.unwrap();
+
+        run(&mut state, "sort id").await.unwrap();
+        run(&mut state, "limit 2").await.unwrap();
+
+        let ids = state.listed_ids();
+        assert_eq!(ids.len(), 2);
+        assert!(ids[0] < ids[1]);
+        assert!(run(&mut state, "sort nope").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_docs_and_lucky_accept_aliases() {
+        let mut state = ShellState::new("stars".to_string(), None).unwrap();
+
+        assert!(run(&mut state, "docs react").await.is_ok());
+        assert!(run(&mut state, "lucky react").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_exit() {
+        let mut state = ShellState::new("stars".to_string(), None).unwrap();
+
+        assert_eq!(run(&mut state, "exit").await.unwrap(), Flow::Exit);
+    }
+}
@@ -0,0 +1,126 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use crossterm::event::{self, Event, KeyEventKind};
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+
+use crate::clients::Context7ClientTrait;
+use crate::core::docs::docs_file_name;
+use crate::models::LibraryId;
+use crate::ui::browser::{Action, Browser};
+use crate::ui::terminal::{self, Screen, TerminalGuard};
+
+/// Browse search results and their documentation in a full-screen interface.
+///
+/// # Arguments
+/// * `client` - Client used for searches and documentation
+/// * `query` - Initial search query
+///
+/// # Returns
+/// * `Ok(())` - When the user quits
+/// * `Err` - If stdin or stdout is not a terminal or the terminal could not be driven
+pub async fn execute<T: Context7ClientTrait>(client: &T, query: String) -> Result<()> {
+    terminal::ensure_terminal(Screen::Stdout)?;
+
+    let mut browser = Browser::new();
+    let response = client.search(&query).await?;
+    browser.set_results(&query, response.results);
+
+    let _guard = TerminalGuard::enter(Screen::Stdout)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+    loop {
+        terminal.draw(|frame| browser.render(frame))?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match browser.handle_key(key) {
+            Action::Continue => {}
+            Action::Quit => break,
+            Action::Search(query) => match client.search(&query).await {
+                Ok(response) => browser.set_results(&query, response.results),
+                Err(err) => browser.set_status(format!("Error: {}", err)),
+            },
+            Action::LoadDocs(id) => match load_docs(client, &id).await {
+                Ok(body) => browser.show_docs(&id, body),
+                Err(err) => browser.set_status(format!("Error: {}", err)),
+            },
+            Action::Copy(text) => {
+                if let Err(err) = copy_to_clipboard(&text) {
+                    browser.set_status(format!("Error: {}", err));
+                }
+            }
+            Action::Save => {
+                let status = match browser.docs() {
+                    Some((id, body)) => match save_docs(Path::new("."), id, body) {
+                        Ok(path) => format!("Saved docs to {}", path.display()),
+                        Err(err) => format!("Error: {}", err),
+                    },
+                    None => "No docs to save".to_string(),
+                };
+                browser.set_status(status);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn load_docs<T: Context7ClientTrait>(client: &T, id: &str) -> Result<String> {
+    client.get_docs(&LibraryId::parse(id)?).await
+}
+
+/// Ask the terminal to put text on the system clipboard with an OSC 52 sequence.
+///
+/// This works over SSH and without a clipboard utility, in terminals that
+/// support the sequence.
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    let mut out = std::io::stdout();
+    write!(out, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Write documentation to `<owner>-<repo>.md` in a directory.
+///
+/// # Returns
+/// * Path of the written file
+fn save_docs(dir: &Path, id: &str, body: &str) -> Result<std::path::PathBuf> {
+    let path = dir.join(docs_file_name(&LibraryId::parse(id)?));
+    std::fs::write(&path, body)
+        .map_err(|err| anyhow::anyhow!("Failed to write {}: {}", path.display(), err))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clients::MockContext7Client;
+
+    #[test]
+    fn test_save_docs_names_file_after_library() {
+        let dir = std::env::temp_dir().join(format!("context7-tui-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = save_docs(&dir, "/vercel/next.js", "# Next.js").unwrap();
+
+        assert_eq!(path, dir.join("vercel-next.js.md"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "# Next.js");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_load_docs_rejects_invalid_id() {
+        let client = MockContext7Client::new();
+
+        assert!(load_docs(&client, "/facebook/react").await.is_ok());
+        assert!(load_docs(&client, "not an id").await.is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{Context, Result};
+use toml_edit::{DocumentMut, Item, Table, value};
+
+use crate::models::LibraryId;
+use crate::models::search::SearchResult;
+
+/// Name of the config file table holding aliases
+const ALIASES_TABLE: &str = "aliases";
+
+/// Check that an alias name can't be mistaken for a library ID, URL or flag.
+pub fn validate_alias_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with(['-', '.'])
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        anyhow::bail!(
+            "Invalid alias '{}'. Aliases may contain letters, digits, '.', '-' and '_', and must not start with '.' or '-'",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Replace an alias with the library ID it stands for.
+///
+/// # Arguments
+/// * `input` - What the user typed where a library ID is expected
+/// * `aliases` - Aliases from the config file
+///
+/// # Returns
+/// * The aliased ID, or `input` unchanged if it isn't an alias
+pub fn expand_alias(input: &str, aliases: &BTreeMap<String, String>) -> String {
+    aliases
+        .get(input.trim())
+        .cloned()
+        .unwrap_or_else(|| input.to_string())
+}
+
+/// Find the aliases that point at each result, for labelling search output.
+///
+/// # Arguments
+/// * `results` - Search results to label
+/// * `aliases` - Aliases from the config file
+///
+/// # Returns
+/// * Comma-separated alias names keyed by result ID, for results that have any
+pub fn alias_labels(
+    results: &[SearchResult],
+    aliases: &BTreeMap<String, String>,
+) -> HashMap<String, String> {
+    if aliases.is_empty() {
+        return HashMap::new();
+    }
+    results
+        .iter()
+        .filter_map(|result| {
+            let id = normalize_id(&result.id);
+            let names: Vec<&str> = aliases
+                .iter()
+                .filter(|(_, target)| normalize_id(target) == id)
+                .map(|(name, _)| name.as_str())
+                .collect();
+            (!names.is_empty()).then(|| (result.id.clone(), names.join(", ")))
+        })
+        .collect()
+}
+
+/// Compare IDs by their canonical form, so "vercel/next.js" matches "/vercel/next.js".
+fn normalize_id(id: &str) -> String {
+    LibraryId::parse(id)
+        .map(|id| id.to_string())
+        .unwrap_or_else(|_| id.to_string())
+}
+
+/// Add or replace an alias in a config file's contents, keeping its comments and layout.
+///
+/// # Arguments
+/// * `contents` - Current config file contents (empty if it doesn't exist yet)
+/// * `name` - Alias to set
+/// * `id` - Library ID it stands for
+///
+/// # Returns
+/// * The updated contents
+/// * `Err` - If the contents aren't valid TOML or `aliases` isn't a table
+pub fn set_alias(contents: &str, name: &str, id: &LibraryId) -> Result<String> {
+    let mut document: DocumentMut = contents.parse().context("Invalid config file")?;
+    let aliases = document
+        .entry(ALIASES_TABLE)
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_like_mut()
+        .ok_or_else(|| anyhow::anyhow!("'{}' in the config file is not a table", ALIASES_TABLE))?;
+    aliases.insert(name, value(id.to_string()));
+    Ok(document.to_string())
+}
+
+/// Remove an alias from a config file's contents, keeping its comments and layout.
+///
+/// # Returns
+/// * `Some(contents)` - The updated contents
+/// * `None` - If there was no such alias
+/// * `Err` - If the contents aren't valid TOML
+pub fn remove_alias(contents: &str, name: &str) -> Result<Option<String>> {
+    let mut document: DocumentMut = contents.parse().context("Invalid config file")?;
+    let removed = document
+        .get_mut(ALIASES_TABLE)
+        .and_then(Item::as_table_like_mut)
+        .and_then(|aliases| aliases.remove(name));
+    Ok(removed.map(|_| document.to_string()))
+}
+
+/// Render aliases as aligned `name  id` lines, sorted by name.
+pub fn format_aliases(aliases: &BTreeMap<String, String>) -> String {
+    let width = aliases.keys().map(|name| name.chars().count()).max();
+    aliases
+        .iter()
+        .map(|(name, id)| format!("{:<width$}  {}", name, id, width = width.unwrap_or(0)))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ConfigFile;
+
+    fn create_test_result(id: &str) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn test_aliases() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("fa".to_string(), "/fastapi/fastapi".to_string()),
+            ("next".to_string(), "vercel/next.js".to_string()),
+            ("nextjs".to_string(), "/vercel/next.js".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_validate_alias_name() {
+        assert!(validate_alias_name("next").is_ok());
+        assert!(validate_alias_name("react-query_v5.2").is_ok());
+        assert!(validate_alias_name("").is_err());
+        assert!(validate_alias_name("vercel/next").is_err());
+        assert!(validate_alias_name("--help").is_err());
+        assert!(validate_alias_name("..").is_err());
+        assert!(validate_alias_name("my alias").is_err());
+    }
+
+    #[test]
+    fn test_expand_alias() {
+        let aliases = test_aliases();
+
+        assert_eq!(expand_alias("fa", &aliases), "/fastapi/fastapi");
+        assert_eq!(expand_alias(" fa ", &aliases), "/fastapi/fastapi");
+        assert_eq!(expand_alias("/pallets/flask", &aliases), "/pallets/flask");
+        assert_eq!(expand_alias("react", &aliases), "react");
+    }
+
+    #[test]
+    fn test_alias_labels_match_normalized_ids() {
+        let results = vec![
+            create_test_result("/vercel/next.js"),
+            create_test_result("/pallets/flask"),
+        ];
+
+        let labels = alias_labels(&results, &test_aliases());
+
+        assert_eq!(labels["/vercel/next.js"], "next, nextjs");
+        assert!(!labels.contains_key("/pallets/flask"));
+    }
+
+    #[test]
+    fn test_set_alias_keeps_existing_contents() {
+        let contents = "# My settings\ntimeout = 10\n\n[aliases]\nfa = \"/fastapi/fastapi\"\n";
+        let id = LibraryId::parse("vercel/next.js").unwrap();
+
+        let updated = set_alias(contents, "next", &id).unwrap();
+
+        assert!(updated.starts_with("# My settings\ntimeout = 10\n"));
+        let config = ConfigFile::parse(&updated).unwrap();
+        assert_eq!(config.settings.timeout, Some(10));
+        assert_eq!(config.aliases["fa"], "/fastapi/fastapi");
+        assert_eq!(config.aliases["next"], "/vercel/next.js");
+    }
+
+    #[test]
+    fn test_set_alias_creates_table() {
+        let id = LibraryId::parse("/fastapi/fastapi").unwrap();
+
+        let updated = set_alias("", "fa", &id).unwrap();
+
+        assert_eq!(updated, "[aliases]\nfa = \"/fastapi/fastapi\"\n");
+    }
+
+    #[test]
+    fn test_remove_alias() {
+        let contents = "[aliases]\nfa = \"/fastapi/fastapi\"\nnext = \"/vercel/next.js\"\n";
+
+        let updated = remove_alias(contents, "fa").unwrap().unwrap();
+
+        assert_eq!(updated, "[aliases]\nnext = \"/vercel/next.js\"\n");
+        assert_eq!(remove_alias(contents, "missing").unwrap(), None);
+        assert_eq!(remove_alias("", "fa").unwrap(), None);
+    }
+
+    #[test]
+    fn test_format_aliases() {
+        assert_eq!(
+            format_aliases(&test_aliases()),
+            "fa      /fastapi/fastapi\nnext    vercel/next.js\nnextjs  /vercel/next.js"
+        );
+        assert_eq!(format_aliases(&BTreeMap::new()), "");
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+
+/// When to style output with colors and text attributes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorChoice {
+    /// Style output written to a terminal, unless `NO_COLOR` is set
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub const ALL: [ColorChoice; 3] = [ColorChoice::Auto, ColorChoice::Always, ColorChoice::Never];
+
+    pub fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|choice| choice.name() == s.to_lowercase())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid color choice '{}'. Valid options are: {}",
+                    s,
+                    Self::ALL.map(|choice| choice.name()).join(", ")
+                )
+            })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorChoice::Auto => "auto",
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+        }
+    }
+
+    /// Decide whether to style output.
+    ///
+    /// # Arguments
+    /// * `is_terminal` - Whether the output goes to a terminal
+    ///
+    /// # Returns
+    /// * `true` if output should be styled
+    pub fn enabled(&self, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                is_terminal && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_choice_from_str() {
+        assert_eq!(ColorChoice::from_str("auto").unwrap(), ColorChoice::Auto);
+        assert_eq!(
+            ColorChoice::from_str("ALWAYS").unwrap(),
+            ColorChoice::Always
+        );
+
+        let error = ColorChoice::from_str("sometimes").unwrap_err().to_string();
+        assert!(error.contains("Valid options are: auto, always, never"));
+    }
+
+    #[test]
+    fn test_color_choice_enabled() {
+        assert!(ColorChoice::Always.enabled(false));
+        assert!(!ColorChoice::Never.enabled(true));
+        assert!(!ColorChoice::Auto.enabled(false));
+    }
+}
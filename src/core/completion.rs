@@ -0,0 +1,145 @@
+use std::io::Write;
+
+use anyhow::Result;
+use clap::Command;
+use clap_complete::aot;
+use clap_complete::env::{Bash, EnvCompleter, Fish, Powershell, Zsh};
+
+/// Environment variable that switches the binary into completion mode
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Name of the installed binary that completion scripts register for
+pub const BIN_NAME: &str = "context7-cli";
+
+/// Shells we can generate completion scripts for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+}
+
+impl CompletionShell {
+    pub const ALL: [CompletionShell; 4] = [
+        CompletionShell::Bash,
+        CompletionShell::Zsh,
+        CompletionShell::Fish,
+        CompletionShell::PowerShell,
+    ];
+
+    pub fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|shell| shell.name() == s.to_lowercase())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid shell '{}'. Valid options are: {}",
+                    s,
+                    Self::ALL.map(|shell| shell.name()).join(", ")
+                )
+            })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CompletionShell::Bash => "bash",
+            CompletionShell::Zsh => "zsh",
+            CompletionShell::Fish => "fish",
+            CompletionShell::PowerShell => "powershell",
+        }
+    }
+}
+
+/// Write a completion script for a shell.
+///
+/// The default script calls back into `completer` on every tab press, so
+/// values such as cached library IDs are completed from the current state.
+/// A static script is generated once from the command definition and only
+/// completes subcommands and flags.
+///
+/// # Arguments
+/// * `shell` - Shell to generate for
+/// * `static_script` - Generate a self-contained script instead of one that calls back
+/// * `command` - The CLI definition
+/// * `completer` - Path of the binary the dynamic script calls back into
+/// * `buf` - Where the script is written
+pub fn write_completion_script(
+    shell: CompletionShell,
+    static_script: bool,
+    command: &mut Command,
+    completer: &str,
+    buf: &mut dyn Write,
+) -> Result<()> {
+    if static_script {
+        let generator = match shell {
+            CompletionShell::Bash => aot::Shell::Bash,
+            CompletionShell::Zsh => aot::Shell::Zsh,
+            CompletionShell::Fish => aot::Shell::Fish,
+            CompletionShell::PowerShell => aot::Shell::PowerShell,
+        };
+        aot::generate(generator, command, BIN_NAME, buf);
+        return Ok(());
+    }
+
+    let completer_shell: &dyn EnvCompleter = match shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::Fish => &Fish,
+        CompletionShell::PowerShell => &Powershell,
+    };
+    completer_shell.write_registration(COMPLETE_VAR, BIN_NAME, BIN_NAME, completer, buf)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_command() -> Command {
+        Command::new(BIN_NAME)
+            .subcommand(Command::new("search"))
+            .subcommand(Command::new("get-docs"))
+    }
+
+    fn script(shell: CompletionShell, static_script: bool) -> String {
+        let mut buf = Vec::new();
+        write_completion_script(
+            shell,
+            static_script,
+            &mut test_command(),
+            "/usr/local/bin/context7-cli",
+            &mut buf,
+        )
+        .unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_completion_shell_from_str() {
+        assert_eq!(
+            CompletionShell::from_str("PowerShell").unwrap(),
+            CompletionShell::PowerShell
+        );
+        let error_msg = CompletionShell::from_str("tcsh").unwrap_err().to_string();
+        assert!(error_msg.contains("Valid options are: bash, zsh, fish, powershell"));
+    }
+
+    #[test]
+    fn test_dynamic_script_calls_back_into_binary() {
+        for shell in CompletionShell::ALL {
+            let script = script(shell, false);
+            assert!(script.contains(COMPLETE_VAR), "{}", shell.name());
+            assert!(script.contains("/usr/local/bin/context7-cli"));
+        }
+    }
+
+    #[test]
+    fn test_static_script_lists_subcommands() {
+        for shell in CompletionShell::ALL {
+            let script = script(shell, true);
+            assert!(script.contains("get-docs"), "{}", shell.name());
+            assert!(!script.contains(COMPLETE_VAR));
+        }
+    }
+}
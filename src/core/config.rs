@@ -0,0 +1,470 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Directory under the user's config directory holding our files
+pub const APP_DIR: &str = "context7-cli";
+
+/// Name of the config file inside `APP_DIR`
+const CONFIG_FILE: &str = "config.toml";
+
+/// Name of the history file inside the data directory
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// Name of the file keeping lines entered in `shell`, inside the data directory
+const SHELL_HISTORY_FILE: &str = "shell_history.txt";
+
+/// Request timeout used unless configured otherwise
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Settings that can appear at the top level of the config file or in a profile
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ProfileSettings {
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    pub timeout: Option<u64>,
+    pub format: Option<String>,
+    pub cache_dir: Option<PathBuf>,
+    pub offline: Option<bool>,
+    pub color: Option<String>,
+    pub sort_by: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Contents of `config.toml`.
+///
+/// Top-level settings apply to every invocation; `[profiles.<name>]` tables
+/// override them when that profile is selected. `[aliases]` maps short names
+/// to library IDs regardless of profile.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigFile {
+    pub default_profile: Option<String>,
+    pub settings: ProfileSettings,
+    pub profiles: BTreeMap<String, ProfileSettings>,
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl ConfigFile {
+    /// Parse a config file's contents.
+    ///
+    /// Unknown keys (e.g. `base_url` instead of `base-url`) are rejected rather
+    /// than ignored. The top-level settings are split off by hand because serde
+    /// can't deny unknown fields next to a flattened struct.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut table: toml::Table = toml::from_str(contents)?;
+        let default_profile = table
+            .remove("default-profile")
+            .map(|value| value.try_into().context("Invalid 'default-profile'"))
+            .transpose()?;
+        let profiles = table
+            .remove("profiles")
+            .map(|value| value.try_into().context("Invalid [profiles] table"))
+            .transpose()?
+            .unwrap_or_default();
+        let aliases = table
+            .remove("aliases")
+            .map(|value| value.try_into().context("Invalid [aliases] table"))
+            .transpose()?
+            .unwrap_or_default();
+        let settings = toml::Value::Table(table).try_into()?;
+
+        Ok(ConfigFile {
+            default_profile,
+            settings,
+            profiles,
+            aliases,
+        })
+    }
+
+    /// Load the config file from an explicit path or the default location.
+    ///
+    /// # Arguments
+    /// * `path` - Explicit config path, which must exist; `None` uses the default path
+    ///
+    /// # Returns
+    /// * The parsed config, or an empty one if the default file doesn't exist
+    /// * `Err` - If the file can't be read or isn't valid TOML
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match default_config_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file '{}'", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("Invalid config file '{}'", path.display()))
+    }
+
+    /// Look up the selected profile, falling back to `default-profile`.
+    ///
+    /// # Returns
+    /// * `Some((name, settings))` - For the selected profile
+    /// * `None` - If no profile was requested and there is no default
+    /// * `Err` - If the requested profile isn't defined
+    pub fn profile(&self, requested: Option<&str>) -> Result<Option<(&str, &ProfileSettings)>> {
+        let Some(name) = requested.or(self.default_profile.as_deref()) else {
+            return Ok(None);
+        };
+        match self.profiles.get_key_value(name) {
+            Some((name, settings)) => Ok(Some((name.as_str(), settings))),
+            None if self.profiles.is_empty() => {
+                anyhow::bail!("Unknown profile '{}'. No profiles are defined", name)
+            }
+            None => anyhow::bail!(
+                "Unknown profile '{}'. Available profiles: {}",
+                name,
+                self.profiles
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// `$XDG_CONFIG_HOME/context7-cli`, falling back to `~/.config/context7-cli`.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?;
+    Some(base.join(APP_DIR))
+}
+
+/// Default location of the config file.
+pub fn default_config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(CONFIG_FILE))
+}
+
+/// `$XDG_DATA_HOME/context7-cli`, falling back to `~/.local/share/context7-cli`.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("share")))?;
+    Some(base.join(APP_DIR))
+}
+
+/// Location of the lookup history file.
+pub fn default_history_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(HISTORY_FILE))
+}
+
+/// Location of the line history of the interactive shell.
+pub fn default_shell_history_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(SHELL_HISTORY_FILE))
+}
+
+/// Where an effective setting came from, from highest to lowest precedence
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingSource {
+    Flag,
+    Env(String),
+    Profile(String),
+    ConfigFile,
+    Default,
+}
+
+impl fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingSource::Flag => write!(f, "flag"),
+            SettingSource::Env(name) => write!(f, "env {}", name),
+            SettingSource::Profile(name) => write!(f, "profile '{}'", name),
+            SettingSource::ConfigFile => write!(f, "config file"),
+            SettingSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// A resolved setting value and where it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: SettingSource,
+}
+
+impl<T> Setting<T> {
+    pub fn new(value: T, source: SettingSource) -> Self {
+        Self { value, source }
+    }
+}
+
+/// Values given on the command line or through the environment
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    pub profile: Option<Setting<String>>,
+    pub base_url: Option<Setting<String>>,
+    pub api_key: Option<Setting<String>>,
+    pub timeout: Option<Setting<u64>>,
+    pub format: Option<Setting<String>>,
+    pub cache_dir: Option<Setting<PathBuf>>,
+    pub offline: Option<Setting<bool>>,
+    pub color: Option<Setting<String>>,
+    pub sort_by: Option<Setting<String>>,
+    pub limit: Option<Setting<usize>>,
+}
+
+/// The effective settings after applying flag > env > profile > config file > default
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub profile: Option<Setting<String>>,
+    pub base_url: Setting<String>,
+    pub api_key: Setting<Option<String>>,
+    pub timeout: Setting<u64>,
+    pub format: Setting<Option<String>>,
+    pub cache_dir: Setting<Option<PathBuf>>,
+    pub offline: Setting<bool>,
+    pub color: Setting<String>,
+    pub sort_by: Setting<String>,
+    pub limit: Setting<Option<usize>>,
+}
+
+impl Settings {
+    /// Resolve every setting from its highest-precedence source.
+    ///
+    /// # Arguments
+    /// * `overrides` - Values set by flags or environment variables
+    /// * `config` - Parsed config file
+    ///
+    /// # Returns
+    /// * The effective settings
+    /// * `Err` - If the requested profile isn't defined in the config file
+    pub fn resolve(overrides: Overrides, config: &ConfigFile) -> Result<Self> {
+        let requested = overrides.profile.as_ref().map(|p| p.value.as_str());
+        let profile = config.profile(requested)?;
+        let profile_source = overrides
+            .profile
+            .as_ref()
+            .map_or(SettingSource::ConfigFile, |p| p.source.clone());
+        let layers = Layers {
+            profile,
+            file: &config.settings,
+        };
+
+        Ok(Settings {
+            profile: profile.map(|(name, _)| Setting::new(name.to_string(), profile_source)),
+            base_url: layers.pick(
+                overrides.base_url,
+                |s| s.base_url.clone(),
+                || crate::clients::DEFAULT_BASE_URL.to_string(),
+            ),
+            api_key: layers.pick_optional(overrides.api_key, |s| s.api_key.clone()),
+            timeout: layers.pick(overrides.timeout, |s| s.timeout, || DEFAULT_TIMEOUT_SECS),
+            format: layers.pick_optional(overrides.format, |s| s.format.clone()),
+            cache_dir: layers.pick_optional(overrides.cache_dir, |s| s.cache_dir.clone()),
+            offline: layers.pick(overrides.offline, |s| s.offline, || false),
+            color: layers.pick(overrides.color, |s| s.color.clone(), || "auto".to_string()),
+            sort_by: layers.pick(
+                overrides.sort_by,
+                |s| s.sort_by.clone(),
+                || "stars".to_string(),
+            ),
+            limit: layers.pick_optional(overrides.limit, |s| s.limit),
+        })
+    }
+}
+
+/// The config file layers below flags and environment variables
+struct Layers<'a> {
+    profile: Option<(&'a str, &'a ProfileSettings)>,
+    file: &'a ProfileSettings,
+}
+
+impl Layers<'_> {
+    fn pick<T>(
+        &self,
+        explicit: Option<Setting<T>>,
+        get: impl Fn(&ProfileSettings) -> Option<T>,
+        default: impl FnOnce() -> T,
+    ) -> Setting<T> {
+        if let Some(setting) = explicit {
+            return setting;
+        }
+        if let Some((name, settings)) = self.profile
+            && let Some(value) = get(settings)
+        {
+            return Setting::new(value, SettingSource::Profile(name.to_string()));
+        }
+        match get(self.file) {
+            Some(value) => Setting::new(value, SettingSource::ConfigFile),
+            None => Setting::new(default(), SettingSource::Default),
+        }
+    }
+
+    /// Like `pick`, for settings with no default value
+    fn pick_optional<T>(
+        &self,
+        explicit: Option<Setting<T>>,
+        get: impl Fn(&ProfileSettings) -> Option<T>,
+    ) -> Setting<Option<T>> {
+        self.pick(
+            explicit.map(|s| Setting::new(Some(s.value), s.source)),
+            |s| get(s).map(Some),
+            || None,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+default-profile = "work"
+timeout = 10
+sort-by = "trustScore"
+
+[profiles.work]
+base-url = "https://context7.internal/api/v1"
+api-key = "secret"
+limit = 5
+offline = true
+color = "never"
+
+[profiles.home]
+sort-by = "relevance"
+
+[aliases]
+fa = "/fastapi/fastapi"
+"#;
+
+    #[test]
+    fn test_parse_config_file() {
+        let config = ConfigFile::parse(SAMPLE).unwrap();
+
+        assert_eq!(config.default_profile, Some("work".to_string()));
+        assert_eq!(config.settings.timeout, Some(10));
+        assert_eq!(config.profiles.len(), 2);
+        assert_eq!(config.profiles["work"].limit, Some(5));
+        assert_eq!(config.aliases["fa"], "/fastapi/fastapi");
+    }
+
+    #[test]
+    fn test_parse_invalid_config_file() {
+        assert!(ConfigFile::parse("timeout = \"soon\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_keys() {
+        let err = ConfigFile::parse("base_url = \"https://example.com\"").unwrap_err();
+        assert!(format!("{:#}", err).contains("unknown field `base_url`"));
+
+        let err = ConfigFile::parse("[profiles.work]\nsortby = \"stars\"").unwrap_err();
+        assert!(format!("{:#}", err).contains("unknown field `sortby`"));
+    }
+
+    #[test]
+    fn test_profile_uses_default_profile() {
+        let config = ConfigFile::parse(SAMPLE).unwrap();
+
+        let (name, _) = config.profile(None).unwrap().unwrap();
+
+        assert_eq!(name, "work");
+        assert_eq!(config.profile(Some("home")).unwrap().unwrap().0, "home");
+    }
+
+    #[test]
+    fn test_unknown_profile_lists_available_profiles() {
+        let config = ConfigFile::parse(SAMPLE).unwrap();
+
+        let error_msg = config.profile(Some("play")).unwrap_err().to_string();
+
+        assert!(error_msg.contains("Unknown profile 'play'"));
+        assert!(error_msg.contains("home, work"));
+    }
+
+    #[test]
+    fn test_resolve_defaults_without_config() {
+        let settings = Settings::resolve(Overrides::default(), &ConfigFile::default()).unwrap();
+
+        assert_eq!(settings.profile, None);
+        assert_eq!(settings.base_url.value, crate::clients::DEFAULT_BASE_URL);
+        assert_eq!(settings.timeout.value, DEFAULT_TIMEOUT_SECS);
+        assert_eq!(settings.sort_by.value, "stars");
+        assert_eq!(settings.limit.value, None);
+        assert_eq!(settings.limit.source, SettingSource::Default);
+        assert_eq!(settings.api_key.value, None);
+        assert!(!settings.offline.value);
+        assert_eq!(settings.color.value, "auto");
+        assert_eq!(settings.cache_dir.value, None);
+    }
+
+    #[test]
+    fn test_resolve_profile_over_config_file() {
+        let config = ConfigFile::parse(SAMPLE).unwrap();
+
+        let settings = Settings::resolve(Overrides::default(), &config).unwrap();
+
+        assert_eq!(
+            settings.profile,
+            Some(Setting::new("work".to_string(), SettingSource::ConfigFile))
+        );
+        assert_eq!(
+            settings.base_url,
+            Setting::new(
+                "https://context7.internal/api/v1".to_string(),
+                SettingSource::Profile("work".to_string())
+            )
+        );
+        assert_eq!(settings.limit.value, Some(5));
+        assert_eq!(settings.api_key.value.as_deref(), Some("secret"));
+        assert!(settings.offline.value);
+        assert_eq!(settings.color.value, "never");
+        assert_eq!(settings.format.source, SettingSource::Default);
+        // Not set in the profile, so the top-level value applies
+        assert_eq!(
+            settings.timeout,
+            Setting::new(10, SettingSource::ConfigFile)
+        );
+        assert_eq!(settings.sort_by.value, "trustScore");
+    }
+
+    #[test]
+    fn test_resolve_flag_and_env_over_profile() {
+        let config = ConfigFile::parse(SAMPLE).unwrap();
+        let overrides = Overrides {
+            profile: Some(Setting::new("home".to_string(), SettingSource::Flag)),
+            limit: Some(Setting::new(20, SettingSource::Flag)),
+            sort_by: Some(Setting::new(
+                "stars".to_string(),
+                SettingSource::Env("CONTEXT7_SORT_BY".to_string()),
+            )),
+            ..Default::default()
+        };
+
+        let settings = Settings::resolve(overrides, &config).unwrap();
+
+        assert_eq!(settings.profile.unwrap().value, "home");
+        assert_eq!(settings.limit, Setting::new(Some(20), SettingSource::Flag));
+        assert_eq!(settings.sort_by.value, "stars");
+        assert_eq!(settings.sort_by.source.to_string(), "env CONTEXT7_SORT_BY");
+    }
+
+    #[test]
+    fn test_setting_source_display() {
+        assert_eq!(SettingSource::Flag.to_string(), "flag");
+        assert_eq!(
+            SettingSource::Profile("work".to_string()).to_string(),
+            "profile 'work'"
+        );
+        assert_eq!(SettingSource::ConfigFile.to_string(), "config file");
+        assert_eq!(SettingSource::Default.to_string(), "default");
+    }
+
+    #[test]
+    fn test_load_missing_explicit_path_fails() {
+        let error = ConfigFile::load(Some(Path::new("/nonexistent/context7/config.toml")))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("Failed to read config file"));
+    }
+}
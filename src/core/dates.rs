@@ -0,0 +1,70 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate};
+
+/// Parse a date as returned by the Context7 API.
+///
+/// Accepts plain dates (`2025-01-15`) as well as RFC 3339 timestamps
+/// (`2025-01-15T10:30:00.000Z`), keeping only the calendar date.
+///
+/// # Arguments
+/// * `s` - Date string to parse
+///
+/// # Returns
+/// * `Some(NaiveDate)` - If the string is a recognizable date
+/// * `None` - If the string is malformed
+pub fn parse_date(s: &str) -> Option<NaiveDate> {
+    let s = s.trim();
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").ok().or_else(|| {
+        DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|dt| dt.date_naive())
+    })
+}
+
+/// Parse a date supplied on the command line, failing with a helpful message.
+///
+/// # Arguments
+/// * `s` - Date string in `YYYY-MM-DD` form
+///
+/// # Returns
+/// * `Ok(NaiveDate)` - If the date is valid
+/// * `Err` - If the date cannot be parsed
+pub fn parse_date_arg(s: &str) -> Result<NaiveDate> {
+    parse_date(s)
+        .ok_or_else(|| anyhow::anyhow!("Invalid date '{}'. Expected format: YYYY-MM-DD", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date_plain() {
+        assert_eq!(
+            parse_date("2025-01-15"),
+            NaiveDate::from_ymd_opt(2025, 1, 15)
+        );
+    }
+
+    #[test]
+    fn test_parse_date_rfc3339_timestamp() {
+        assert_eq!(
+            parse_date("2025-01-15T23:30:00.000Z"),
+            NaiveDate::from_ymd_opt(2025, 1, 15)
+        );
+    }
+
+    #[test]
+    fn test_parse_date_malformed() {
+        assert_eq!(parse_date("yesterday"), None);
+        assert_eq!(parse_date("2025-13-01"), None);
+        assert_eq!(parse_date(""), None);
+    }
+
+    #[test]
+    fn test_parse_date_arg_error_message() {
+        let error_msg = parse_date_arg("01/15/2025").unwrap_err().to_string();
+        assert!(error_msg.contains("Invalid date '01/15/2025'"));
+        assert!(error_msg.contains("YYYY-MM-DD"));
+    }
+}
@@ -0,0 +1,155 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::models::LibraryId;
+
+/// Documentation fetched for a library, as printed by `get-docs --json`
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryDocs {
+    /// Normalized library ID (e.g. "/fastapi/fastapi")
+    pub id: String,
+    /// The markdown exactly as fetched
+    pub content: String,
+}
+
+/// A fenced code block found in a documentation body
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeBlock {
+    /// Line index of the opening fence
+    pub start: usize,
+    /// Line index of the closing fence, or the last line if the fence is never closed
+    pub end: usize,
+    /// Language named after the opening fence, if any
+    pub language: Option<String>,
+    /// Lines between the fences
+    pub code: String,
+}
+
+/// Find the fenced (```` ``` ```` or `~~~`) code blocks in documentation text.
+///
+/// # Arguments
+/// * `lines` - Documentation split into lines
+///
+/// # Returns
+/// * Code blocks in the order they appear
+pub fn code_blocks(lines: &[String]) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(usize, &str, Option<String>)> = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        match &open {
+            None => {
+                for fence in ["```", "~~~"] {
+                    if let Some(info) = trimmed.strip_prefix(fence) {
+                        let language = info
+                            .trim_start_matches(&fence[..1])
+                            .split_whitespace()
+                            .next()
+                            .map(str::to_string);
+                        open = Some((index, fence, language));
+                        break;
+                    }
+                }
+            }
+            Some((start, fence, language)) => {
+                if trimmed.starts_with(fence)
+                    && trimmed.trim_start_matches(&fence[..1]).trim().is_empty()
+                {
+                    blocks.push(CodeBlock {
+                        start: *start,
+                        end: index,
+                        language: language.clone(),
+                        code: lines[start + 1..index].join("\n"),
+                    });
+                    open = None;
+                }
+            }
+        }
+    }
+
+    if let Some((start, _, language)) = open {
+        blocks.push(CodeBlock {
+            start,
+            end: lines.len().saturating_sub(1),
+            language,
+            code: lines[start + 1..].join("\n"),
+        });
+    }
+    blocks
+}
+
+/// Indexes of the lines containing a term, ignoring ASCII case.
+pub fn matching_lines(lines: &[String], term: &str) -> Vec<usize> {
+    if term.is_empty() {
+        return Vec::new();
+    }
+    let term = term.to_ascii_lowercase();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.to_ascii_lowercase().contains(&term))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// File name documentation for a library is saved under, e.g. `vercel-next.js.md`.
+pub fn docs_file_name(id: &LibraryId) -> String {
+    match id.version() {
+        Some(version) => format!("{}-{}-{}.md", id.owner(), id.repo(), version),
+        None => format!("{}-{}.md", id.owner(), id.repo()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_code_blocks_records_language_and_code() {
+        let doc = lines("# Title\n```rust\nfn main() {}\n```\ntext\n~~~\nplain\n~~~");
+
+        let blocks = code_blocks(&doc);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].start, 1);
+        assert_eq!(blocks[0].end, 3);
+        assert_eq!(blocks[0].language.as_deref(), Some("rust"));
+        assert_eq!(blocks[0].code, "fn main() {}");
+        assert_eq!(blocks[1].language, None);
+        assert_eq!(blocks[1].code, "plain");
+    }
+
+    #[test]
+    fn test_code_blocks_keeps_unclosed_block_and_other_fences() {
+        let doc = lines("```md\n~~~\nnot a fence end\n```text");
+
+        let blocks = code_blocks(&doc);
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].end, 3);
+        assert_eq!(blocks[0].code, "~~~\nnot a fence end\n```text");
+    }
+
+    #[test]
+    fn test_matching_lines_ignores_case() {
+        let doc = lines("Install\nusage\nRun the INSTALLER");
+
+        assert_eq!(matching_lines(&doc, "install"), vec![0, 2]);
+        assert!(matching_lines(&doc, "").is_empty());
+    }
+
+    #[test]
+    fn test_docs_file_name() {
+        let id = LibraryId::parse("/vercel/next.js").unwrap();
+        let versioned = LibraryId::parse("/vercel/next.js/v14.3.0").unwrap();
+
+        assert_eq!(docs_file_name(&id), "vercel-next.js.md");
+        assert_eq!(docs_file_name(&versioned), "vercel-next.js-v14.3.0.md");
+    }
+}
@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::models::search::SearchResult;
+
+/// Fields of a search result that can be selected for output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Branch,
+    Description,
+    Id,
+    LastUpdateDate,
+    Stars,
+    State,
+    Title,
+    TotalPages,
+    TotalSnippets,
+    TotalTokens,
+    TrustScore,
+    Versions,
+    Alias,
+}
+
+impl Field {
+    /// Fields of `SearchResult`, in declaration order; the default output columns
+    pub const RESULT: [Field; 12] = [
+        Field::Branch,
+        Field::Description,
+        Field::Id,
+        Field::LastUpdateDate,
+        Field::Stars,
+        Field::State,
+        Field::Title,
+        Field::TotalPages,
+        Field::TotalSnippets,
+        Field::TotalTokens,
+        Field::TrustScore,
+        Field::Versions,
+    ];
+
+    /// Every selectable field: those of `SearchResult`, then the local `alias`
+    pub const ALL: [Field; 13] = [
+        Field::Branch,
+        Field::Description,
+        Field::Id,
+        Field::LastUpdateDate,
+        Field::Stars,
+        Field::State,
+        Field::Title,
+        Field::TotalPages,
+        Field::TotalSnippets,
+        Field::TotalTokens,
+        Field::TrustScore,
+        Field::Versions,
+        Field::Alias,
+    ];
+
+    /// Parse a string into a Field
+    pub fn from_str(s: &str) -> Result<Self> {
+        Field::ALL
+            .into_iter()
+            .find(|field| field.name() == s)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid field '{}'. Valid options are: {}",
+                    s,
+                    Field::ALL.map(|field| field.name()).join(", ")
+                )
+            })
+    }
+
+    /// Name of the field as it appears in serialized output
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Branch => "branch",
+            Field::Description => "description",
+            Field::Id => "id",
+            Field::LastUpdateDate => "lastUpdateDate",
+            Field::Stars => "stars",
+            Field::State => "state",
+            Field::Title => "title",
+            Field::TotalPages => "totalPages",
+            Field::TotalSnippets => "totalSnippets",
+            Field::TotalTokens => "totalTokens",
+            Field::TrustScore => "trustScore",
+            Field::Versions => "versions",
+            Field::Alias => "alias",
+        }
+    }
+}
+
+/// Parse a comma-separated list of field names.
+///
+/// # Arguments
+/// * `s` - Field names separated by commas (e.g. "id,title,stars")
+///
+/// # Returns
+/// * Fields in the order given
+/// * `Err` - If any name is not a valid field or the list is empty
+pub fn parse_field_list(s: &str) -> Result<Vec<Field>> {
+    let fields = s
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(Field::from_str)
+        .collect::<Result<Vec<Field>>>()?;
+
+    if fields.is_empty() {
+        anyhow::bail!("At least one field must be specified");
+    }
+    Ok(fields)
+}
+
+/// Extract the serialized value of each requested field from a result.
+///
+/// Values are read from the serde representation of `SearchResult`, so the
+/// names and shapes always match the JSON output. `alias` isn't part of the
+/// result and is looked up in `aliases` instead.
+///
+/// # Arguments
+/// * `result` - Search result to read from
+/// * `fields` - Fields to extract
+/// * `aliases` - Alias labels keyed by result ID
+///
+/// # Returns
+/// * One JSON value per field, `Value::Null` for missing values
+pub fn field_values(
+    result: &SearchResult,
+    fields: &[Field],
+    aliases: &HashMap<String, String>,
+) -> Result<Vec<Value>> {
+    let serialized = serde_json::to_value(result)?;
+    Ok(fields
+        .iter()
+        .map(|field| match field {
+            Field::Alias => aliases
+                .get(&result.id)
+                .map_or(Value::Null, |names| Value::String(names.clone())),
+            _ => serialized.get(field.name()).cloned().unwrap_or(Value::Null),
+        })
+        .collect())
+}
+
+/// Project a result down to the requested fields as a JSON object.
+///
+/// # Arguments
+/// * `result` - Search result to project
+/// * `fields` - Fields to keep, in output order
+/// * `aliases` - Alias labels keyed by result ID
+///
+/// # Returns
+/// * JSON object containing only the requested fields
+pub fn project(
+    result: &SearchResult,
+    fields: &[Field],
+    aliases: &HashMap<String, String>,
+) -> Result<Value> {
+    let values = field_values(result, fields, aliases)?;
+    Ok(Value::Object(
+        fields
+            .iter()
+            .map(|field| field.name().to_string())
+            .zip(values)
+            .collect(),
+    ))
+}
+
+/// Project every result down to the requested fields.
+///
+/// # Arguments
+/// * `results` - Search results to project
+/// * `fields` - Fields to keep, in output order
+/// * `aliases` - Alias labels keyed by result ID
+///
+/// # Returns
+/// * One JSON object per result
+pub fn project_results(
+    results: &[SearchResult],
+    fields: &[Field],
+    aliases: &HashMap<String, String>,
+) -> Result<Vec<Value>> {
+    results
+        .iter()
+        .map(|r| project(r, fields, aliases))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_result() -> SearchResult {
+        SearchResult {
+            id: "/test/lib".to_string(),
+            description: Some("A test library".to_string()),
+            stars: Some(42),
+            title: Some("Test".to_string()),
+            trust_score: Some(7.5),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_field_from_str_valid() {
+        assert_eq!(Field::from_str("id").unwrap(), Field::Id);
+        assert_eq!(Field::from_str("trustScore").unwrap(), Field::TrustScore);
+        assert_eq!(
+            Field::from_str("lastUpdateDate").unwrap(),
+            Field::LastUpdateDate
+        );
+    }
+
+    #[test]
+    fn test_field_from_str_invalid() {
+        let result = Field::from_str("trust_score");
+        assert!(result.is_err());
+        let error_msg = result.unwrap_err().to_string();
+        assert!(error_msg.contains("Invalid field 'trust_score'"));
+        assert!(error_msg.contains("trustScore"));
+    }
+
+    #[test]
+    fn test_field_names_match_serialized_keys() {
+        let serialized = serde_json::to_value(create_test_result()).unwrap();
+        let keys: Vec<&String> = serialized.as_object().unwrap().keys().collect();
+
+        assert_eq!(keys.len(), Field::RESULT.len());
+        for field in Field::RESULT {
+            assert!(keys.iter().any(|k| k.as_str() == field.name()));
+        }
+    }
+
+    #[test]
+    fn test_parse_field_list_preserves_order() {
+        let fields = parse_field_list("stars, id,title").unwrap();
+        assert_eq!(fields, vec![Field::Stars, Field::Id, Field::Title]);
+    }
+
+    #[test]
+    fn test_parse_field_list_rejects_unknown() {
+        assert!(parse_field_list("id,bogus").is_err());
+    }
+
+    #[test]
+    fn test_parse_field_list_rejects_empty() {
+        assert!(parse_field_list(" , ").is_err());
+    }
+
+    #[test]
+    fn test_field_values_extracts_requested_fields() {
+        let values = field_values(
+            &create_test_result(),
+            &[Field::Id, Field::Stars, Field::Branch, Field::Alias],
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(values[0], Value::String("/test/lib".to_string()));
+        assert_eq!(values[1], serde_json::json!(42));
+        assert_eq!(values[2], Value::Null);
+        assert_eq!(values[3], Value::Null);
+    }
+
+    #[test]
+    fn test_field_values_reads_alias_from_labels() {
+        let aliases = HashMap::from([("/test/lib".to_string(), "t, test".to_string())]);
+
+        let values = field_values(&create_test_result(), &[Field::Alias], &aliases).unwrap();
+
+        assert_eq!(values[0], Value::String("t, test".to_string()));
+    }
+
+    #[test]
+    fn test_project_keeps_only_requested_fields_in_order() {
+        let projected = project(
+            &create_test_result(),
+            &[Field::TrustScore, Field::Id],
+            &HashMap::new(),
+        )
+        .unwrap();
+        let object = projected.as_object().unwrap();
+
+        assert_eq!(object.len(), 2);
+        assert_eq!(
+            object.keys().collect::<Vec<&String>>(),
+            vec!["trustScore", "id"]
+        );
+        assert_eq!(object["id"], serde_json::json!("/test/lib"));
+    }
+}
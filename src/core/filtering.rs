@@ -0,0 +1,231 @@
+use chrono::NaiveDate;
+
+use crate::core::dates::parse_date;
+use crate::models::EnvelopeFilters;
+use crate::models::search::SearchResult;
+
+/// A condition a search result must satisfy to be kept
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    MinStars(i64),
+    MinTrustScore(f64),
+    MinSnippets(i64),
+    State(String),
+    UpdatedSince(NaiveDate),
+}
+
+impl Filter {
+    /// Check whether a result satisfies this filter.
+    ///
+    /// Results missing the relevant field never satisfy a threshold.
+    pub fn matches(&self, result: &SearchResult) -> bool {
+        match self {
+            Filter::MinStars(min) => result.stars.is_some_and(|stars| stars >= *min),
+            Filter::MinTrustScore(min) => result.trust_score.is_some_and(|trust| trust >= *min),
+            Filter::MinSnippets(min) => result
+                .total_snippets
+                .is_some_and(|snippets| snippets >= *min),
+            Filter::State(state) => result
+                .state
+                .as_deref()
+                .is_some_and(|s| s.eq_ignore_ascii_case(state)),
+            Filter::UpdatedSince(since) => result
+                .last_update_date
+                .as_deref()
+                .and_then(parse_date)
+                .is_some_and(|updated| updated >= *since),
+        }
+    }
+}
+
+/// Keep only the results that satisfy every filter.
+///
+/// # Arguments
+/// * `results` - Vector of search results to filter
+/// * `filters` - Filters to apply; an empty slice keeps everything
+///
+/// # Returns
+/// * Results matching all filters, in their original order
+pub fn apply_filters(results: Vec<SearchResult>, filters: &[Filter]) -> Vec<SearchResult> {
+    results
+        .into_iter()
+        .filter(|result| filters.iter().all(|filter| filter.matches(result)))
+        .collect()
+}
+
+/// Describe the applied filters for a JSON envelope.
+///
+/// # Arguments
+/// * `filters` - Filters that were applied to the results
+///
+/// # Returns
+/// * The filter settings, keyed by their command-line names
+pub fn describe_filters(filters: &[Filter]) -> EnvelopeFilters {
+    let mut described = EnvelopeFilters::default();
+    for filter in filters {
+        match filter {
+            Filter::MinStars(min) => described.min_stars = Some(*min),
+            Filter::MinTrustScore(min) => described.min_trust = Some(*min),
+            Filter::MinSnippets(min) => described.min_snippets = Some(*min),
+            Filter::State(state) => described.state = Some(state.clone()),
+            Filter::UpdatedSince(since) => described.updated_since = Some(since.to_string()),
+        }
+    }
+    described
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_result(
+        id: &str,
+        stars: Option<i64>,
+        trust_score: Option<f64>,
+        total_snippets: Option<i64>,
+        state: Option<&str>,
+        last_update_date: Option<&str>,
+    ) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            last_update_date: last_update_date.map(|s| s.to_string()),
+            stars,
+            state: state.map(|s| s.to_string()),
+            total_snippets,
+            trust_score,
+            ..Default::default()
+        }
+    }
+
+    fn ids(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_min_stars_filter() {
+        let results = vec![
+            create_test_result("low", Some(10), None, None, None, None),
+            create_test_result("high", Some(5000), None, None, None, None),
+            create_test_result("exact", Some(1000), None, None, None, None),
+        ];
+
+        let filtered = apply_filters(results, &[Filter::MinStars(1000)]);
+
+        assert_eq!(ids(&filtered), vec!["high", "exact"]);
+    }
+
+    #[test]
+    fn test_min_trust_score_filter() {
+        let results = vec![
+            create_test_result("low", None, Some(5.5), None, None, None),
+            create_test_result("high", None, Some(9.0), None, None, None),
+        ];
+
+        let filtered = apply_filters(results, &[Filter::MinTrustScore(7.0)]);
+
+        assert_eq!(ids(&filtered), vec!["high"]);
+    }
+
+    #[test]
+    fn test_min_snippets_filter() {
+        let results = vec![
+            create_test_result("few", None, None, Some(10), None, None),
+            create_test_result("many", None, None, Some(500), None, None),
+        ];
+
+        let filtered = apply_filters(results, &[Filter::MinSnippets(50)]);
+
+        assert_eq!(ids(&filtered), vec!["many"]);
+    }
+
+    #[test]
+    fn test_state_filter_is_case_insensitive() {
+        let results = vec![
+            create_test_result("done", None, None, None, Some("finalized"), None),
+            create_test_result("pending", None, None, None, Some("initial"), None),
+        ];
+
+        let filtered = apply_filters(results, &[Filter::State("Finalized".to_string())]);
+
+        assert_eq!(ids(&filtered), vec!["done"]);
+    }
+
+    #[test]
+    fn test_updated_since_filter_handles_timestamps() {
+        let results = vec![
+            create_test_result("old", None, None, None, None, Some("2024-06-01")),
+            create_test_result(
+                "new",
+                None,
+                None,
+                None,
+                None,
+                Some("2025-03-01T12:00:00.000Z"),
+            ),
+            create_test_result("bad", None, None, None, None, Some("not a date")),
+        ];
+
+        let since = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let filtered = apply_filters(results, &[Filter::UpdatedSince(since)]);
+
+        assert_eq!(ids(&filtered), vec!["new"]);
+    }
+
+    #[test]
+    fn test_missing_values_do_not_satisfy_thresholds() {
+        let results = vec![create_test_result("none", None, None, None, None, None)];
+
+        assert!(apply_filters(results.clone(), &[Filter::MinStars(0)]).is_empty());
+        assert!(apply_filters(results.clone(), &[Filter::MinTrustScore(0.0)]).is_empty());
+        assert!(apply_filters(results, &[Filter::State("finalized".to_string())]).is_empty());
+    }
+
+    #[test]
+    fn test_filters_compose_with_and() {
+        let results = vec![
+            create_test_result("popular_untrusted", Some(5000), Some(3.0), None, None, None),
+            create_test_result("popular_trusted", Some(5000), Some(9.0), None, None, None),
+            create_test_result("niche_trusted", Some(10), Some(9.0), None, None, None),
+        ];
+
+        let filtered = apply_filters(
+            results,
+            &[Filter::MinStars(1000), Filter::MinTrustScore(7.0)],
+        );
+
+        assert_eq!(ids(&filtered), vec!["popular_trusted"]);
+    }
+
+    #[test]
+    fn test_no_filters_keeps_everything() {
+        let results = vec![
+            create_test_result("a", None, None, None, None, None),
+            create_test_result("b", Some(1), None, None, None, None),
+        ];
+
+        let filtered = apply_filters(results, &[]);
+
+        assert_eq!(filtered.len(), 2);
+    }
+
+    #[test]
+    fn test_describe_filters() {
+        let filters = vec![
+            Filter::MinStars(100),
+            Filter::State("finalized".to_string()),
+            Filter::UpdatedSince(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap()),
+        ];
+
+        let described = describe_filters(&filters);
+
+        assert_eq!(described.min_stars, Some(100));
+        assert_eq!(described.min_trust, None);
+        assert_eq!(described.state, Some("finalized".to_string()));
+        assert_eq!(described.updated_since, Some("2025-01-01".to_string()));
+    }
+
+    #[test]
+    fn test_describe_no_filters() {
+        assert_eq!(describe_filters(&[]), EnvelopeFilters::default());
+    }
+}
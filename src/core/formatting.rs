@@ -1,23 +1,186 @@
+use std::collections::HashMap;
+use std::path::Path;
+
 use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+use crate::core::config::{SettingSource, Settings};
+use crate::core::fields::{Field, field_values, project_results};
+use crate::core::scoring::{ScoreWeights, ScoredResult};
+use crate::models::SearchEnvelope;
 use crate::models::search::SearchResult;
 
+/// Fallback terminal width used when the real width cannot be determined
+pub const DEFAULT_TABLE_WIDTH: usize = 120;
+
+/// Descriptions narrower than this are dropped from the table entirely
+const MIN_DESCRIPTION_WIDTH: usize = 10;
+
+/// Separator placed between table columns
+const COLUMN_SEPARATOR: &str = "  ";
+
+/// Columns shown by the table format when no fields are selected
+const DEFAULT_TABLE_FIELDS: [Field; 7] = [
+    Field::Id,
+    Field::Title,
+    Field::Stars,
+    Field::TrustScore,
+    Field::TotalSnippets,
+    Field::LastUpdateDate,
+    Field::Description,
+];
+
+/// Output formats supported by the search command
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    Ids,
+    Table,
+    Csv,
+    Tsv,
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Parse a string into an OutputFormat
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(OutputFormat::Json),
+            "ids" => Ok(OutputFormat::Ids),
+            "table" => Ok(OutputFormat::Table),
+            "csv" => Ok(OutputFormat::Csv),
+            "tsv" => Ok(OutputFormat::Tsv),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            _ => anyhow::bail!(
+                "Invalid output format '{}'. Valid options are: json, ids, table, csv, tsv, ndjson",
+                s
+            ),
+        }
+    }
+
+    /// Whether this format can be narrowed with a field selection
+    pub fn supports_fields(&self) -> bool {
+        !matches!(self, OutputFormat::Ids)
+    }
+}
+
+/// What the search command prints: the results alone, or wrapped in an envelope
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchFormat {
+    Results(OutputFormat),
+    /// Rendered by `format_envelope`, since only the caller knows the query and options
+    JsonEnvelope,
+}
+
+impl SearchFormat {
+    /// Parse a string into a SearchFormat
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json-envelope" => Ok(SearchFormat::JsonEnvelope),
+            _ => match OutputFormat::from_str(s) {
+                Ok(format) => Ok(SearchFormat::Results(format)),
+                Err(_) => anyhow::bail!(
+                    "Invalid output format '{}'. Valid options are: json, ids, table, csv, tsv, ndjson, json-envelope",
+                    s
+                ),
+            },
+        }
+    }
+
+    /// Whether this format can be narrowed with a field selection
+    pub fn supports_fields(&self) -> bool {
+        match self {
+            SearchFormat::Results(format) => format.supports_fields(),
+            SearchFormat::JsonEnvelope => true,
+        }
+    }
+}
+
 /// Format search results based on the output mode.
 ///
 /// # Arguments
 /// * `results` - Search results to format
-/// * `id_only` - If true, output only IDs (one per line); otherwise output JSON
+/// * `format` - Output format to render
+/// * `fields` - Fields to include; empty selects the format's default set
+/// * `aliases` - Alias labels keyed by result ID
+/// * `width` - Available terminal width (only used by the table format)
 ///
 /// # Returns
 /// * Formatted string ready for output
-pub fn format_search_results(results: &[SearchResult], id_only: bool) -> Result<String> {
-    if id_only {
-        Ok(format_ids_only(results))
+pub fn format_search_results(
+    results: &[SearchResult],
+    format: OutputFormat,
+    fields: &[Field],
+    aliases: &HashMap<String, String>,
+    width: usize,
+) -> Result<String> {
+    // Machine-readable defaults never include `alias`, so their columns stay stable
+    let all_fields = if fields.is_empty() {
+        &Field::RESULT[..]
     } else {
-        format_json_pretty(results)
+        fields
+    };
+
+    match format {
+        OutputFormat::Ids => Ok(format_ids_only(results)),
+        OutputFormat::Json if fields.is_empty() => format_json_pretty(results),
+        OutputFormat::Json => format_json_pretty(&project_results(results, fields, aliases)?),
+        OutputFormat::Table if fields.is_empty() => {
+            let mut table_fields = DEFAULT_TABLE_FIELDS.to_vec();
+            if results
+                .iter()
+                .any(|result| aliases.contains_key(&result.id))
+            {
+                table_fields.insert(1, Field::Alias);
+            }
+            format_table(results, &table_fields, aliases, width)
+        }
+        OutputFormat::Table => format_table(results, fields, aliases, width),
+        OutputFormat::Csv => format_delimited(results, all_fields, aliases, ',', escape_csv),
+        OutputFormat::Tsv => format_delimited(results, all_fields, aliases, '\t', escape_tsv),
+        OutputFormat::Ndjson if fields.is_empty() => format_ndjson(results),
+        OutputFormat::Ndjson => format_ndjson(&project_results(results, fields, aliases)?),
     }
 }
 
+/// Format search results wrapped in a self-describing envelope.
+///
+/// # Arguments
+/// * `envelope` - Query metadata to include
+/// * `fields` - Fields to include in each result; empty keeps every field
+/// * `aliases` - Alias labels keyed by result ID
+///
+/// # Returns
+/// * Pretty-printed JSON object with the metadata and results
+pub fn format_envelope(
+    envelope: SearchEnvelope,
+    fields: &[Field],
+    aliases: &HashMap<String, String>,
+) -> Result<String> {
+    if fields.is_empty() {
+        return Ok(serde_json::to_string_pretty(&envelope)?);
+    }
+
+    let projected = SearchEnvelope {
+        results: project_results(&envelope.results, fields, aliases)?,
+        schema_version: envelope.schema_version,
+        query: envelope.query,
+        sort_by: envelope.sort_by,
+        weights: envelope.weights,
+        missing: envelope.missing,
+        ignore_case: envelope.ignore_case,
+        filters: envelope.filters,
+        limit: envelope.limit,
+        page: envelope.page,
+        fetched_at: envelope.fetched_at,
+        count: envelope.count,
+        total: envelope.total,
+    };
+    Ok(serde_json::to_string_pretty(&projected)?)
+}
+
 /// Format search results as a list of IDs (one per line).
 ///
 /// # Arguments
@@ -33,17 +196,588 @@ pub fn format_ids_only(results: &[SearchResult]) -> String {
         .join("\n")
 }
 
-/// Format search results as pretty-printed JSON.
+/// Format search results (or projections of them) as pretty-printed JSON.
 ///
 /// # Arguments
 /// * `results` - Search results to format
 ///
 /// # Returns
 /// * Pretty-printed JSON string
-pub fn format_json_pretty(results: &[SearchResult]) -> Result<String> {
+pub fn format_json_pretty<T: Serialize>(results: &[T]) -> Result<String> {
     Ok(serde_json::to_string_pretty(results)?)
 }
 
+/// Format search results (or projections of them) as newline-delimited JSON.
+///
+/// # Arguments
+/// * `results` - Search results to format
+///
+/// # Returns
+/// * String with one compact JSON object per line
+pub fn format_ndjson<T: Serialize>(results: &[T]) -> Result<String> {
+    let lines = results
+        .iter()
+        .map(serde_json::to_string)
+        .collect::<serde_json::Result<Vec<String>>>()?;
+    Ok(lines.join("\n"))
+}
+
+/// Format search results as delimiter-separated values with a header row.
+///
+/// # Arguments
+/// * `results` - Search results to format
+/// * `fields` - Columns to include, in order
+/// * `aliases` - Alias labels keyed by result ID
+/// * `delimiter` - Character placed between cells
+/// * `escape` - Escaping applied to each header and cell
+///
+/// # Returns
+/// * Header row of field names followed by one row per result
+pub fn format_delimited(
+    results: &[SearchResult],
+    fields: &[Field],
+    aliases: &HashMap<String, String>,
+    delimiter: char,
+    escape: fn(&str) -> String,
+) -> Result<String> {
+    let separator = delimiter.to_string();
+    let mut lines = vec![
+        fields
+            .iter()
+            .map(|field| escape(field.name()))
+            .collect::<Vec<String>>()
+            .join(&separator),
+    ];
+
+    for result in results {
+        let cells = field_values(result, fields, aliases)?;
+        lines.push(
+            cells
+                .iter()
+                .map(|value| escape(&cell_text(value)))
+                .collect::<Vec<String>>()
+                .join(&separator),
+        );
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Render a JSON value as plain cell text (empty for null, compact JSON for arrays).
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Quote a CSV cell per RFC 4180 when it contains a delimiter, quote or newline.
+fn escape_csv(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// Escape tabs, newlines and backslashes so a TSV cell stays on one line.
+fn escape_tsv(cell: &str) -> String {
+    cell.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// A piece of a parsed output template
+#[derive(Debug, Clone, PartialEq)]
+enum TemplateSegment {
+    Literal(String),
+    Placeholder(Field),
+}
+
+/// A user-defined output template such as `{{id}} ({{stars}}★): {{description}}`.
+///
+/// Placeholders name a `SearchResult` field using its serialized name and are
+/// validated when the template is parsed, so typos fail before any request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<TemplateSegment>,
+}
+
+impl Template {
+    /// Parse a template string into literal text and field placeholders.
+    ///
+    /// # Arguments
+    /// * `source` - Template text using `{{field}}` placeholders
+    ///
+    /// # Returns
+    /// * `Ok(Template)` - If every placeholder names a valid field
+    /// * `Err` - If a placeholder is unknown, empty or left unclosed
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(TemplateSegment::Literal(rest[..start].to_string()));
+            }
+            let after_open = &rest[start + 2..];
+            let Some(end) = after_open.find("}}") else {
+                anyhow::bail!("Unclosed placeholder in template: '{}'", &rest[start..]);
+            };
+            let name = after_open[..end].trim();
+            if name.is_empty() {
+                anyhow::bail!("Empty placeholder '{{{{}}}}' in template");
+            }
+            let field = Field::from_str(name).map_err(|_| unknown_placeholder_error(name))?;
+            segments.push(TemplateSegment::Placeholder(field));
+            rest = &after_open[end + 2..];
+        }
+
+        if !rest.is_empty() {
+            segments.push(TemplateSegment::Literal(rest.to_string()));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Render the template for a single result; missing values render as empty text.
+    ///
+    /// # Arguments
+    /// * `result` - Search result to render
+    /// * `aliases` - Alias labels keyed by result ID, for `{{alias}}`
+    pub fn render(
+        &self,
+        result: &SearchResult,
+        aliases: &HashMap<String, String>,
+    ) -> Result<String> {
+        let serialized = serde_json::to_value(result)?;
+        Ok(self
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                TemplateSegment::Literal(text) => text.clone(),
+                TemplateSegment::Placeholder(Field::Alias) => {
+                    aliases.get(&result.id).cloned().unwrap_or_default()
+                }
+                TemplateSegment::Placeholder(field) => serialized
+                    .get(field.name())
+                    .map(cell_text)
+                    .unwrap_or_default(),
+            })
+            .collect())
+    }
+}
+
+/// Build an error for an unknown placeholder, suggesting the closest field name.
+fn unknown_placeholder_error(name: &str) -> anyhow::Error {
+    let normalized = name.replace(['_', '-'], "").to_lowercase();
+    let suggestion = Field::ALL
+        .into_iter()
+        .find(|field| field.name().to_lowercase() == normalized);
+    let valid = Field::ALL.map(|field| field.name()).join(", ");
+
+    match suggestion {
+        Some(field) => anyhow::anyhow!(
+            "Unknown placeholder '{{{{{}}}}}' in template. Did you mean '{{{{{}}}}}'? Valid fields are: {}",
+            name,
+            field.name(),
+            valid
+        ),
+        None => anyhow::anyhow!(
+            "Unknown placeholder '{{{{{}}}}}' in template. Valid fields are: {}",
+            name,
+            valid
+        ),
+    }
+}
+
+/// Render every result with a template, one rendered result per line.
+///
+/// # Arguments
+/// * `results` - Search results to format
+/// * `template` - Parsed template applied to each result
+/// * `aliases` - Alias labels keyed by result ID
+///
+/// # Returns
+/// * Rendered results joined by newlines
+pub fn format_with_template(
+    results: &[SearchResult],
+    template: &Template,
+    aliases: &HashMap<String, String>,
+) -> Result<String> {
+    let rendered = results
+        .iter()
+        .map(|result| template.render(result, aliases))
+        .collect::<Result<Vec<String>>>()?;
+    Ok(rendered.join("\n"))
+}
+
+/// Describe the effective settings and where each one came from.
+///
+/// # Arguments
+/// * `settings` - Resolved settings
+/// * `config_path` - Config file location, if one could be determined
+///
+/// # Returns
+/// * The config file line followed by one aligned `name  value  (source)` row per setting
+pub fn format_settings(settings: &Settings, config_path: Option<&Path>) -> String {
+    let config_line = match config_path {
+        Some(path) if path.exists() => format!("Config file: {}", path.display()),
+        Some(path) => format!("Config file: {} (not found)", path.display()),
+        None => "Config file: none (no home directory)".to_string(),
+    };
+
+    let (profile, profile_source) = match &settings.profile {
+        Some(profile) => (profile.value.clone(), profile.source.clone()),
+        None => ("none".to_string(), SettingSource::Default),
+    };
+    let or_none = |value: Option<String>| value.unwrap_or_else(|| "none".to_string());
+    let rows = [
+        ("profile", profile, &profile_source),
+        (
+            "base-url",
+            settings.base_url.value.clone(),
+            &settings.base_url.source,
+        ),
+        (
+            // Never print the key itself
+            "api-key",
+            or_none(settings.api_key.value.as_ref().map(|_| "set".to_string())),
+            &settings.api_key.source,
+        ),
+        (
+            "timeout",
+            settings.timeout.value.to_string(),
+            &settings.timeout.source,
+        ),
+        (
+            "format",
+            or_none(settings.format.value.clone()),
+            &settings.format.source,
+        ),
+        (
+            "cache-dir",
+            or_none(
+                settings
+                    .cache_dir
+                    .value
+                    .as_ref()
+                    .map(|dir| dir.display().to_string()),
+            ),
+            &settings.cache_dir.source,
+        ),
+        (
+            "offline",
+            settings.offline.value.to_string(),
+            &settings.offline.source,
+        ),
+        (
+            "color",
+            settings.color.value.clone(),
+            &settings.color.source,
+        ),
+        (
+            "sort-by",
+            settings.sort_by.value.clone(),
+            &settings.sort_by.source,
+        ),
+        (
+            "limit",
+            or_none(settings.limit.value.map(|limit| limit.to_string())),
+            &settings.limit.source,
+        ),
+    ];
+
+    let name_width = rows
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let value_width = rows
+        .iter()
+        .map(|(_, value, _)| value.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![config_line];
+    for (name, value, source) in rows {
+        lines.push(format!(
+            "{:<name_width$}  {:<value_width$}  ({})",
+            name,
+            value,
+            source,
+            name_width = name_width,
+            value_width = value_width
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Explain how lucky ranked its candidates and why the winner was chosen.
+///
+/// # Arguments
+/// * `query` - The query the candidates were scored against
+/// * `ranked` - Candidates ordered from most to least relevant
+/// * `weights` - Weights used to combine the score components
+///
+/// # Returns
+/// * A table of each candidate's normalized components and total, followed
+///   by a sentence naming the winner and what set it apart
+pub fn format_explanation(query: &str, ranked: &[ScoredResult], weights: &ScoreWeights) -> String {
+    let mut lines = vec![format!(
+        "Candidates for '{}' (weights: name={}, trust={}, stars={}, snippets={}):",
+        query, weights.name, weights.trust, weights.stars, weights.snippets
+    )];
+
+    let id_width = ranked
+        .iter()
+        .map(|scored| scored.result.id.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("ID".len());
+
+    lines.push(format!(
+        "{:>3}  {:<id_width$}  {:>5}  {:>5}  {:>5}  {:>8}  {:>6}",
+        "#",
+        "ID",
+        "NAME",
+        "TRUST",
+        "STARS",
+        "SNIPPETS",
+        "TOTAL",
+        id_width = id_width
+    ));
+    for (rank, scored) in ranked.iter().enumerate() {
+        let score = &scored.score;
+        lines.push(format!(
+            "{:>3}  {:<id_width$}  {:>5.2}  {:>5.2}  {:>5.2}  {:>8.2}  {:>6.2}",
+            rank + 1,
+            scored.result.id,
+            score.name,
+            score.trust,
+            score.stars,
+            score.snippets,
+            score.total,
+            id_width = id_width
+        ));
+    }
+
+    if let Some(reason) = explain_winner(ranked, weights) {
+        lines.push(reason);
+    }
+
+    lines.join("\n")
+}
+
+/// Describe why the top candidate won, naming the component that gave it the biggest lead.
+fn explain_winner(ranked: &[ScoredResult], weights: &ScoreWeights) -> Option<String> {
+    let winner = ranked.first()?;
+    let Some(runner_up) = ranked.get(1) else {
+        return Some(format!(
+            "Chose {}: it was the only candidate (score {:.2}).",
+            winner.result.id, winner.score.total
+        ));
+    };
+
+    let (w, r) = (&winner.score, &runner_up.score);
+    let contributions = [
+        ("name match", weights.name * (w.name - r.name)),
+        ("trust score", weights.trust * (w.trust - r.trust)),
+        ("stars", weights.stars * (w.stars - r.stars)),
+        ("snippets", weights.snippets * (w.snippets - r.snippets)),
+    ];
+    let (component, lead) = contributions
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or(("name match", 0.0));
+
+    let margin = w.total - r.total;
+    if margin <= 0.0 {
+        return Some(format!(
+            "Chose {}: tied with {} at {:.2}, broken by ID order.",
+            winner.result.id, runner_up.result.id, w.total
+        ));
+    }
+
+    Some(format!(
+        "Chose {}: highest score {:.2}, {:.2} ahead of {}, mostly from {} (+{:.2}).",
+        winner.result.id, w.total, margin, runner_up.result.id, component, lead
+    ))
+}
+
+/// Format search results as an aligned, human-readable table.
+///
+/// Columns are sized to their widest cell. The description column fills
+/// whatever width remains and is truncated with an ellipsis; it is omitted
+/// when too little room is left.
+///
+/// # Arguments
+/// * `results` - Search results to format
+/// * `fields` - Columns to include, in order
+/// * `aliases` - Alias labels keyed by result ID
+/// * `width` - Total width available for each line
+///
+/// # Returns
+/// * Table with a header row followed by one row per result
+pub fn format_table(
+    results: &[SearchResult],
+    fields: &[Field],
+    aliases: &HashMap<String, String>,
+    width: usize,
+) -> Result<String> {
+    let mut rows: Vec<Vec<String>> = Vec::with_capacity(results.len());
+    for result in results {
+        let values = field_values(result, fields, aliases)?;
+        rows.push(
+            fields
+                .iter()
+                .zip(&values)
+                .map(|(field, value)| single_line(&table_cell(*field, value)))
+                .collect(),
+        );
+    }
+
+    let mut widths: Vec<usize> = fields
+        .iter()
+        .map(|field| table_header(*field).width())
+        .collect();
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.width());
+        }
+    }
+
+    // The description absorbs (or gives up) whatever width the other columns leave
+    let description_column = fields.iter().position(|f| *f == Field::Description);
+    let mut visible: Vec<bool> = vec![true; fields.len()];
+    if let Some(column) = description_column {
+        let fixed_width: usize = widths
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != column)
+            .map(|(_, w)| w + COLUMN_SEPARATOR.len())
+            .sum();
+        let description_width = width.saturating_sub(fixed_width);
+        if description_width >= MIN_DESCRIPTION_WIDTH {
+            widths[column] = widths[column].min(description_width);
+        } else {
+            visible[column] = false;
+        }
+    }
+
+    let render_line = |cells: Vec<String>| -> String {
+        cells
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| visible[*i])
+            .map(|(i, cell)| {
+                pad(
+                    &truncate(cell, widths[i]),
+                    widths[i],
+                    is_right_aligned(fields[i]),
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(COLUMN_SEPARATOR)
+            .trim_end()
+            .to_string()
+    };
+
+    let mut lines = vec![render_line(
+        fields
+            .iter()
+            .map(|f| table_header(*f).to_string())
+            .collect(),
+    )];
+    lines.extend(rows.into_iter().map(render_line));
+
+    Ok(lines.join("\n"))
+}
+
+/// Column heading used for a field in table output.
+fn table_header(field: Field) -> &'static str {
+    match field {
+        Field::Branch => "BRANCH",
+        Field::Description => "DESCRIPTION",
+        Field::Id => "ID",
+        Field::LastUpdateDate => "UPDATED",
+        Field::Stars => "STARS",
+        Field::State => "STATE",
+        Field::Title => "TITLE",
+        Field::TotalPages => "PAGES",
+        Field::TotalSnippets => "SNIPPETS",
+        Field::TotalTokens => "TOKENS",
+        Field::TrustScore => "TRUST",
+        Field::Versions => "VERSIONS",
+        Field::Alias => "ALIAS",
+    }
+}
+
+/// Numeric columns are right-aligned so their digits line up.
+fn is_right_aligned(field: Field) -> bool {
+    matches!(
+        field,
+        Field::Stars
+            | Field::TotalPages
+            | Field::TotalSnippets
+            | Field::TotalTokens
+            | Field::TrustScore
+    )
+}
+
+/// Render a field value for a table cell, using "-" for missing values.
+fn table_cell(field: Field, value: &Value) -> String {
+    match (field, value) {
+        (_, Value::Null) => "-".to_string(),
+        (Field::TrustScore, Value::Number(n)) => n
+            .as_f64()
+            .map(|t| format!("{:.1}", t))
+            .unwrap_or_else(|| n.to_string()),
+        // Only the date portion of an ISO timestamp is useful at a glance
+        (Field::LastUpdateDate, Value::String(s)) => s.chars().take(10).collect(),
+        _ => cell_text(value),
+    }
+}
+
+/// Pad a cell to `width` terminal columns, aligning left or right.
+fn pad(cell: &str, width: usize, right_aligned: bool) -> String {
+    // `format!` padding counts chars, which is wrong for wide CJK and emoji
+    let padding = " ".repeat(width.saturating_sub(cell.width()));
+    if right_aligned {
+        format!("{}{}", padding, cell)
+    } else {
+        format!("{}{}", cell, padding)
+    }
+}
+
+/// Collapse whitespace runs, newlines included, so a cell can't break the table layout.
+fn single_line(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Truncate a string to at most `max` terminal columns, ending in an ellipsis when shortened.
+fn truncate(s: &str, max: usize) -> String {
+    if s.width() <= max {
+        return s.to_string();
+    }
+    // Leave one column for the ellipsis
+    let budget = max.saturating_sub(1);
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let width = c.width().unwrap_or(0);
+        if used + width > budget {
+            break;
+        }
+        truncated.push(c);
+        used += width;
+    }
+    truncated.push('…');
+    truncated
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,17 +785,9 @@ mod tests {
     fn create_test_result(id: &str, title: Option<&str>, stars: Option<i64>) -> SearchResult {
         SearchResult {
             id: id.to_string(),
-            branch: None,
-            description: None,
-            last_update_date: None,
             stars,
-            state: None,
             title: title.map(|s| s.to_string()),
-            total_pages: None,
-            total_snippets: None,
-            total_tokens: None,
-            trust_score: None,
-            versions: None,
+            ..Default::default()
         }
     }
 
@@ -154,14 +880,28 @@ mod tests {
             create_test_result("/first/lib", None, None),
             create_test_result("/second/lib", None, None),
         ];
-        let output = format_search_results(&results, true).unwrap();
+        let output = format_search_results(
+            &results,
+            OutputFormat::Ids,
+            &[],
+            &HashMap::new(),
+            DEFAULT_TABLE_WIDTH,
+        )
+        .unwrap();
         assert_eq!(output, "/first/lib\n/second/lib");
     }
 
     #[test]
     fn test_format_search_results_json_mode() {
         let results = vec![create_test_result("/test/lib", Some("Test"), Some(100))];
-        let output = format_search_results(&results, false).unwrap();
+        let output = format_search_results(
+            &results,
+            OutputFormat::Json,
+            &[],
+            &HashMap::new(),
+            DEFAULT_TABLE_WIDTH,
+        )
+        .unwrap();
 
         assert!(output.contains("\"id\": \"/test/lib\""));
         assert!(output.contains("\"title\": \"Test\""));
@@ -171,14 +911,631 @@ mod tests {
     #[test]
     fn test_format_search_results_empty_with_id_only() {
         let results: Vec<SearchResult> = vec![];
-        let output = format_search_results(&results, true).unwrap();
+        let output = format_search_results(
+            &results,
+            OutputFormat::Ids,
+            &[],
+            &HashMap::new(),
+            DEFAULT_TABLE_WIDTH,
+        )
+        .unwrap();
         assert_eq!(output, "");
     }
 
     #[test]
     fn test_format_search_results_empty_with_json() {
         let results: Vec<SearchResult> = vec![];
-        let output = format_search_results(&results, false).unwrap();
+        let output = format_search_results(
+            &results,
+            OutputFormat::Json,
+            &[],
+            &HashMap::new(),
+            DEFAULT_TABLE_WIDTH,
+        )
+        .unwrap();
         assert_eq!(output, "[]");
     }
+
+    #[test]
+    fn test_output_format_from_str_valid() {
+        assert_eq!(OutputFormat::from_str("json").unwrap(), OutputFormat::Json);
+        assert_eq!(OutputFormat::from_str("ids").unwrap(), OutputFormat::Ids);
+        assert_eq!(
+            OutputFormat::from_str("table").unwrap(),
+            OutputFormat::Table
+        );
+    }
+
+    #[test]
+    fn test_output_format_from_str_invalid() {
+        let result = OutputFormat::from_str("xml");
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("Invalid output format")
+        );
+    }
+
+    #[test]
+    fn test_format_table_aligns_columns() {
+        let mut first = create_test_result("/facebook/react", Some("React"), Some(220000));
+        first.trust_score = Some(9.5);
+        first.total_snippets = Some(850);
+        first.last_update_date = Some("2025-01-15T10:00:00.000Z".to_string());
+        let second = create_test_result("/vuejs/core", Some("Vue.js"), Some(45000));
+
+        let output = format_table(
+            &[first, second],
+            &DEFAULT_TABLE_FIELDS,
+            &HashMap::new(),
+            200,
+        )
+        .unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("ID"));
+        assert!(lines[0].contains("DESCRIPTION"));
+        assert!(lines[1].contains("220000"));
+        assert!(lines[1].contains("9.5"));
+        assert!(lines[1].contains("2025-01-15"));
+        assert!(!lines[1].contains("T10:00"));
+
+        // Right-aligned numeric columns end at the same offset on every row
+        let stars_end = lines[0].find("STARS").unwrap() + "STARS".len();
+        assert_eq!(&lines[1][stars_end - 6..stars_end], "220000");
+        assert_eq!(&lines[2][stars_end - 5..stars_end], "45000");
+    }
+
+    #[test]
+    fn test_format_table_truncates_description_to_width() {
+        let mut result = create_test_result("/test/lib", Some("Test"), Some(1));
+        result.description = Some("a".repeat(500));
+
+        let output = format_table(&[result], &DEFAULT_TABLE_FIELDS, &HashMap::new(), 100).unwrap();
+
+        for line in output.lines() {
+            assert!(line.width() <= 100);
+        }
+        assert!(output.contains('…'));
+    }
+
+    #[test]
+    fn test_format_table_measures_wide_characters_in_columns() {
+        let mut wide = create_test_result("/test/cjk", Some("日本語ライブラリ"), Some(1));
+        wide.description = Some("漢字の説明🚀".repeat(20));
+        let mut multiline = create_test_result("/test/lib", Some("Two\nlines"), Some(1));
+        multiline.description = Some("Plain".to_string());
+
+        let output = format_table(
+            &[wide, multiline],
+            &DEFAULT_TABLE_FIELDS,
+            &HashMap::new(),
+            80,
+        )
+        .unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.width() <= 80));
+        assert!(lines[2].contains("Two lines"));
+        // Columns after the wide title start at the same terminal column on every row
+        let stars_column = |line: &str| line[..line.find("  1").unwrap()].width();
+        assert_eq!(stars_column(lines[1]), stars_column(lines[2]));
+    }
+
+    #[test]
+    fn test_truncate_counts_terminal_columns() {
+        assert_eq!(truncate("日本語", 6), "日本語");
+        assert_eq!(truncate("日本語", 5), "日本…");
+        assert_eq!(truncate("日本語", 4), "日…");
+    }
+
+    #[test]
+    fn test_format_table_omits_description_when_narrow() {
+        let mut result = create_test_result("/test/lib", Some("Test"), Some(1));
+        result.description = Some("Some description".to_string());
+
+        let output = format_table(&[result], &DEFAULT_TABLE_FIELDS, &HashMap::new(), 20).unwrap();
+
+        assert!(!output.contains("DESCRIPTION"));
+        assert!(!output.contains("Some description"));
+    }
+
+    #[test]
+    fn test_format_table_missing_values_render_as_dash() {
+        let results = vec![create_test_result("/test/lib", None, None)];
+        let output = format_table(&results, &DEFAULT_TABLE_FIELDS, &HashMap::new(), 200).unwrap();
+        let row = output.lines().nth(1).unwrap();
+
+        assert!(row.contains('-'));
+        assert!(row.starts_with("/test/lib"));
+    }
+
+    #[test]
+    fn test_format_table_empty_results_has_header_only() {
+        let results: Vec<SearchResult> = vec![];
+        let output = format_table(&results, &DEFAULT_TABLE_FIELDS, &HashMap::new(), 200).unwrap();
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.starts_with("ID"));
+    }
+
+    #[test]
+    fn test_output_format_from_str_delimited() {
+        assert_eq!(OutputFormat::from_str("csv").unwrap(), OutputFormat::Csv);
+        assert_eq!(OutputFormat::from_str("tsv").unwrap(), OutputFormat::Tsv);
+        assert_eq!(
+            OutputFormat::from_str("ndjson").unwrap(),
+            OutputFormat::Ndjson
+        );
+    }
+
+    fn create_test_envelope(results: Vec<SearchResult>) -> SearchEnvelope {
+        SearchEnvelope {
+            schema_version: crate::models::ENVELOPE_SCHEMA_VERSION,
+            query: "react".to_string(),
+            sort_by: "stars".to_string(),
+            weights: None,
+            missing: Some("last".to_string()),
+            ignore_case: Some(false),
+            filters: Default::default(),
+            limit: None,
+            page: None,
+            fetched_at: "2025-01-15T10:00:00Z".to_string(),
+            count: results.len(),
+            total: None,
+            results,
+        }
+    }
+
+    #[test]
+    fn test_search_format_from_str() {
+        assert_eq!(
+            SearchFormat::from_str("json-envelope").unwrap(),
+            SearchFormat::JsonEnvelope
+        );
+        assert_eq!(
+            SearchFormat::from_str("csv").unwrap(),
+            SearchFormat::Results(OutputFormat::Csv)
+        );
+        let err = SearchFormat::from_str("xml").unwrap_err().to_string();
+        assert!(err.contains("json-envelope"));
+    }
+
+    #[test]
+    fn test_format_envelope_includes_metadata() {
+        let results = vec![create_test_result(
+            "/facebook/react",
+            Some("React"),
+            Some(1),
+        )];
+
+        let output = format_envelope(create_test_envelope(results), &[], &HashMap::new()).unwrap();
+        let json: Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(json["schemaVersion"], 1);
+        assert_eq!(json["query"], "react");
+        assert_eq!(json["count"], 1);
+        assert_eq!(json["results"][0]["title"], "React");
+    }
+
+    #[test]
+    fn test_format_envelope_projects_fields() {
+        let results = vec![create_test_result(
+            "/facebook/react",
+            Some("React"),
+            Some(1),
+        )];
+
+        let output =
+            format_envelope(create_test_envelope(results), &[Field::Id], &HashMap::new()).unwrap();
+        let json: Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(
+            json["results"][0],
+            serde_json::json!({"id": "/facebook/react"})
+        );
+        assert_eq!(json["sortBy"], "stars");
+        assert_eq!(json["missing"], "last");
+    }
+
+    #[test]
+    fn test_format_csv_uses_stable_column_order() {
+        let results = vec![create_test_result("/test/lib", Some("Test"), Some(10))];
+        let output =
+            format_search_results(&results, OutputFormat::Csv, &[], &HashMap::new(), 0).unwrap();
+        let header = output.lines().next().unwrap();
+
+        assert_eq!(
+            header,
+            "branch,description,id,lastUpdateDate,stars,state,title,totalPages,totalSnippets,totalTokens,trustScore,versions"
+        );
+        assert_eq!(output.lines().nth(1).unwrap(), ",,/test/lib,,10,,Test,,,,,");
+    }
+
+    #[test]
+    fn test_format_alias_only_when_selected() {
+        let results = vec![create_test_result("/test/lib", Some("Test"), Some(10))];
+        let aliases = HashMap::from([("/test/lib".to_string(), "tl".to_string())]);
+
+        let csv = format_search_results(&results, OutputFormat::Csv, &[], &aliases, 0).unwrap();
+        assert!(!csv.lines().next().unwrap().contains("alias"));
+        let json = format_search_results(&results, OutputFormat::Json, &[], &aliases, 0).unwrap();
+        assert!(!json.contains("alias"));
+
+        let selected = format_search_results(
+            &results,
+            OutputFormat::Csv,
+            &[Field::Id, Field::Alias],
+            &aliases,
+            0,
+        )
+        .unwrap();
+        assert_eq!(selected, "id,alias\n/test/lib,tl");
+
+        let table =
+            format_search_results(&results, OutputFormat::Table, &[], &aliases, 200).unwrap();
+        assert!(table.starts_with("ID         ALIAS  TITLE"));
+    }
+
+    #[test]
+    fn test_format_csv_selected_fields() {
+        let results = vec![create_test_result("/test/lib", Some("Test"), Some(10))];
+        let output = format_search_results(
+            &results,
+            OutputFormat::Csv,
+            &[Field::Stars, Field::Id],
+            &HashMap::new(),
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(output, "stars,id\n10,/test/lib");
+    }
+
+    #[test]
+    fn test_format_csv_quotes_special_characters() {
+        let mut result = create_test_result("/test/lib", Some("Say \"hi\", please"), None);
+        result.description = Some("line one\nline two".to_string());
+
+        let output = format_delimited(
+            &[result],
+            &[Field::Title, Field::Description],
+            &HashMap::new(),
+            ',',
+            escape_csv,
+        )
+        .unwrap();
+
+        assert_eq!(
+            output,
+            "title,description\n\"Say \"\"hi\"\", please\",\"line one\nline two\""
+        );
+    }
+
+    #[test]
+    fn test_format_tsv_escapes_tabs_and_newlines() {
+        let mut result = create_test_result("/test/lib", Some("a\tb"), None);
+        result.description = Some("one\ntwo\\three".to_string());
+
+        let output = format_search_results(
+            &[result],
+            OutputFormat::Tsv,
+            &[Field::Id, Field::Title, Field::Description],
+            &HashMap::new(),
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            output,
+            "id\ttitle\tdescription\n/test/lib\ta\\tb\tone\\ntwo\\\\three"
+        );
+    }
+
+    #[test]
+    fn test_format_csv_serializes_versions_as_json() {
+        let mut result = create_test_result("/test/lib", None, None);
+        result.versions = Some(vec![serde_json::json!("1.0"), serde_json::json!("2.0")]);
+
+        let output = format_delimited(
+            &[result],
+            &[Field::Versions],
+            &HashMap::new(),
+            ',',
+            escape_csv,
+        )
+        .unwrap();
+
+        assert_eq!(output, "versions\n\"[\"\"1.0\"\",\"\"2.0\"\"]\"");
+    }
+
+    #[test]
+    fn test_format_ndjson_one_object_per_line() {
+        let results = vec![
+            create_test_result("/first/lib", Some("First"), Some(1)),
+            create_test_result("/second/lib", Some("Second"), Some(2)),
+        ];
+        let output = format_ndjson(&results).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        let first: SearchResult = serde_json::from_str(lines[0]).unwrap();
+        let second: SearchResult = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(first.id, "/first/lib");
+        assert_eq!(second.id, "/second/lib");
+    }
+
+    #[test]
+    fn test_format_ndjson_empty_results() {
+        let results: Vec<SearchResult> = vec![];
+        assert_eq!(format_ndjson(&results).unwrap(), "");
+    }
+
+    #[test]
+    fn test_format_json_with_fields_projects_results() {
+        let results = vec![create_test_result("/test/lib", Some("Test"), Some(10))];
+        let output = format_search_results(
+            &results,
+            OutputFormat::Json,
+            &[Field::Stars, Field::Id],
+            &HashMap::new(),
+            0,
+        )
+        .unwrap();
+
+        let parsed: Vec<serde_json::Map<String, Value>> = serde_json::from_str(&output).unwrap();
+        let keys: Vec<&String> = parsed[0].keys().collect();
+        assert_eq!(keys, vec!["stars", "id"]);
+        assert!(!output.contains("versions"));
+    }
+
+    #[test]
+    fn test_format_ndjson_with_fields_projects_results() {
+        let results = vec![
+            create_test_result("/first/lib", Some("First"), Some(1)),
+            create_test_result("/second/lib", None, Some(2)),
+        ];
+        let output = format_search_results(
+            &results,
+            OutputFormat::Ndjson,
+            &[Field::Id, Field::Title],
+            &HashMap::new(),
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            output,
+            "{\"id\":\"/first/lib\",\"title\":\"First\"}\n{\"id\":\"/second/lib\",\"title\":null}"
+        );
+    }
+
+    #[test]
+    fn test_format_table_with_fields_shows_selected_columns() {
+        let results = vec![create_test_result("/test/lib", Some("Test"), Some(10))];
+        let output = format_search_results(
+            &results,
+            OutputFormat::Table,
+            &[Field::Id, Field::Stars],
+            &HashMap::new(),
+            200,
+        )
+        .unwrap();
+
+        let header = output.lines().next().unwrap();
+        assert_eq!(header, "ID         STARS");
+        assert!(!output.contains("TITLE"));
+    }
+
+    #[test]
+    fn test_output_format_supports_fields() {
+        assert!(OutputFormat::Json.supports_fields());
+        assert!(OutputFormat::Table.supports_fields());
+        assert!(!OutputFormat::Ids.supports_fields());
+        assert!(SearchFormat::JsonEnvelope.supports_fields());
+        assert!(!SearchFormat::Results(OutputFormat::Ids).supports_fields());
+    }
+
+    #[test]
+    fn test_template_renders_placeholders() {
+        let mut result = create_test_result("/test/lib", Some("Test"), Some(1200));
+        result.description = Some("A library".to_string());
+
+        let template = Template::parse("{{id}} ({{stars}}★): {{description}}").unwrap();
+
+        assert_eq!(
+            template.render(&result, &HashMap::new()).unwrap(),
+            "/test/lib (1200★): A library"
+        );
+    }
+
+    #[test]
+    fn test_template_allows_whitespace_in_placeholders() {
+        let result = create_test_result("/test/lib", Some("Test"), None);
+        let template = Template::parse("[{{ title }}]").unwrap();
+        assert_eq!(template.render(&result, &HashMap::new()).unwrap(), "[Test]");
+    }
+
+    #[test]
+    fn test_template_missing_values_render_empty() {
+        let result = create_test_result("/test/lib", None, None);
+        let template = Template::parse("{{id}}|{{title}}|{{trustScore}}").unwrap();
+        assert_eq!(
+            template.render(&result, &HashMap::new()).unwrap(),
+            "/test/lib||"
+        );
+    }
+
+    #[test]
+    fn test_template_without_placeholders() {
+        let result = create_test_result("/test/lib", None, None);
+        let template = Template::parse("plain text").unwrap();
+        assert_eq!(
+            template.render(&result, &HashMap::new()).unwrap(),
+            "plain text"
+        );
+    }
+
+    #[test]
+    fn test_template_unknown_placeholder_lists_fields() {
+        let error_msg = Template::parse("{{popularity}}").unwrap_err().to_string();
+        assert!(error_msg.contains("Unknown placeholder '{{popularity}}'"));
+        assert!(error_msg.contains("trustScore"));
+        assert!(!error_msg.contains("Did you mean"));
+    }
+
+    #[test]
+    fn test_template_unknown_placeholder_suggests_field() {
+        let error_msg = Template::parse("{{trust_score}}").unwrap_err().to_string();
+        assert!(error_msg.contains("Did you mean '{{trustScore}}'?"));
+    }
+
+    #[test]
+    fn test_template_unclosed_placeholder() {
+        let error_msg = Template::parse("{{id} oops").unwrap_err().to_string();
+        assert!(error_msg.contains("Unclosed placeholder"));
+    }
+
+    #[test]
+    fn test_template_empty_placeholder() {
+        let error_msg = Template::parse("{{ }}").unwrap_err().to_string();
+        assert!(error_msg.contains("Empty placeholder '{{}}'"));
+    }
+
+    #[test]
+    fn test_format_with_template_one_line_per_result() {
+        let results = vec![
+            create_test_result("/first/lib", Some("First"), None),
+            create_test_result("/second/lib", Some("Second"), None),
+        ];
+        let template = Template::parse("{{title}}={{id}}").unwrap();
+
+        let output = format_with_template(&results, &template, &HashMap::new()).unwrap();
+
+        assert_eq!(output, "First=/first/lib\nSecond=/second/lib");
+    }
+
+    fn scored(id: &str, name: f64, trust: f64, stars: f64, snippets: f64) -> ScoredResult {
+        let weights = ScoreWeights::default();
+        ScoredResult {
+            result: create_test_result(id, None, None),
+            score: crate::core::scoring::ScoreBreakdown {
+                name,
+                trust,
+                stars,
+                snippets,
+                total: weights.name * name
+                    + weights.trust * trust
+                    + weights.stars * stars
+                    + weights.snippets * snippets,
+            },
+        }
+    }
+
+    #[test]
+    fn test_format_explanation_lists_candidates_in_rank_order() {
+        let ranked = vec![
+            scored("/pmndrs/zustand", 1.0, 0.8, 0.77, 0.5),
+            scored("/facebook/react", 0.0, 0.95, 0.89, 0.73),
+        ];
+
+        let output = format_explanation("zustand", &ranked, &ScoreWeights::default());
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[0].contains("Candidates for 'zustand'"));
+        assert!(lines[0].contains("name=4"));
+        assert!(lines[1].contains("NAME"));
+        assert!(lines[1].contains("TOTAL"));
+        assert!(lines[2].trim_start().starts_with("1  /pmndrs/zustand"));
+        assert!(lines[2].contains("1.00"));
+        assert!(lines[3].trim_start().starts_with("2  /facebook/react"));
+    }
+
+    #[test]
+    fn test_format_explanation_names_deciding_component() {
+        let ranked = vec![
+            scored("/pmndrs/zustand", 1.0, 0.8, 0.77, 0.5),
+            scored("/facebook/react", 0.0, 0.95, 0.89, 0.73),
+        ];
+
+        let output = format_explanation("zustand", &ranked, &ScoreWeights::default());
+        let reason = output.lines().last().unwrap();
+
+        assert!(reason.starts_with("Chose /pmndrs/zustand"));
+        assert!(reason.contains("ahead of /facebook/react"));
+        assert!(reason.contains("mostly from name match (+4.00)"));
+    }
+
+    #[test]
+    fn test_format_explanation_single_candidate() {
+        let ranked = vec![scored("/only/lib", 0.5, 0.5, 0.5, 0.5)];
+
+        let output = format_explanation("lib", &ranked, &ScoreWeights::default());
+
+        assert!(output.contains("only candidate"));
+    }
+
+    #[test]
+    fn test_format_explanation_tie() {
+        let ranked = vec![
+            scored("/a/lib", 0.5, 0.5, 0.5, 0.5),
+            scored("/b/lib", 0.5, 0.5, 0.5, 0.5),
+        ];
+
+        let output = format_explanation("lib", &ranked, &ScoreWeights::default());
+
+        assert!(output.contains("tied with /b/lib"));
+    }
+
+    #[test]
+    fn test_format_settings_lists_values_and_sources() {
+        use crate::core::config::Setting;
+
+        let settings = Settings {
+            profile: Some(Setting::new("work".to_string(), SettingSource::Flag)),
+            base_url: Setting::new(
+                "https://context7.internal".to_string(),
+                SettingSource::Profile("work".to_string()),
+            ),
+            api_key: Setting::new(
+                Some("secret".to_string()),
+                SettingSource::Env("CONTEXT7_API_KEY".to_string()),
+            ),
+            timeout: Setting::new(30, SettingSource::Default),
+            format: Setting::new(None, SettingSource::Default),
+            cache_dir: Setting::new(Some("/tmp/c7".into()), SettingSource::Flag),
+            offline: Setting::new(false, SettingSource::Default),
+            color: Setting::new("never".to_string(), SettingSource::Flag),
+            sort_by: Setting::new("stars".to_string(), SettingSource::ConfigFile),
+            limit: Setting::new(Some(5), SettingSource::Env("CONTEXT7_LIMIT".to_string())),
+        };
+
+        let output = format_settings(&settings, Some(Path::new("/nonexistent/config.toml")));
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "Config file: /nonexistent/config.toml (not found)"
+        );
+        assert_eq!(lines[1], "profile    work                       (flag)");
+        assert_eq!(
+            lines[2],
+            "base-url   https://context7.internal  (profile 'work')"
+        );
+        assert_eq!(
+            lines[3],
+            "api-key    set                        (env CONTEXT7_API_KEY)"
+        );
+        assert!(!output.contains("secret"));
+        assert_eq!(lines[5], "format     none                       (default)");
+        assert_eq!(lines[6], "cache-dir  /tmp/c7                    (flag)");
+        assert_eq!(lines[8], "color      never                      (flag)");
+        assert_eq!(
+            lines[10],
+            "limit      5                          (env CONTEXT7_LIMIT)"
+        );
+    }
 }
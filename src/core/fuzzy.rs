@@ -0,0 +1,124 @@
+/// Bonus for each matched character that directly follows the previous match
+const CONSECUTIVE_BONUS: i64 = 8;
+
+/// Bonus for a match at the start of the text or right after a separator
+const BOUNDARY_BONUS: i64 = 10;
+
+/// Penalty for each skipped character between matches
+const GAP_PENALTY: i64 = 1;
+
+/// Score how well a pattern fuzzily matches a piece of text.
+///
+/// Every pattern character must appear in the text in order (case-insensitive).
+/// Matches that are consecutive or start a word (after `/`, `-`, `_`, `.` or
+/// whitespace) score higher; gaps between matches score lower.
+///
+/// # Arguments
+/// * `pattern` - What the user typed
+/// * `text` - Candidate text to match against
+///
+/// # Returns
+/// * `Some(score)` - If every pattern character was found (higher is better)
+/// * `None` - If the pattern does not match
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for pattern_char in pattern.chars().flat_map(char::to_lowercase) {
+        if pattern_char.is_whitespace() {
+            continue;
+        }
+        let offset = text[position..].iter().position(|c| *c == pattern_char)?;
+        let index = position + offset;
+
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += CONSECUTIVE_BONUS;
+        } else if previous_match.is_some() {
+            score -= GAP_PENALTY * offset as i64;
+        }
+        if index == 0 || matches!(text[index - 1], '/' | '-' | '_' | '.' | ' ') {
+            score += BOUNDARY_BONUS;
+        }
+
+        previous_match = Some(index);
+        position = index + 1;
+    }
+
+    Some(score)
+}
+
+/// Find which candidates match a pattern, best matches first.
+///
+/// # Arguments
+/// * `pattern` - What the user typed; an empty pattern matches everything
+/// * `candidates` - Text for each candidate
+///
+/// # Returns
+/// * Indices into `candidates`, ordered by score (ties keep their original order)
+pub fn fuzzy_filter<S: AsRef<str>>(pattern: &str, candidates: &[S]) -> Vec<usize> {
+    let mut matches: Vec<(usize, i64)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(index, text)| fuzzy_score(pattern, text.as_ref()).map(|s| (index, s)))
+        .collect();
+
+    // sort_by is stable, so equal scores keep the incoming (e.g. relevance) order
+    matches.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    matches.into_iter().map(|(index, _)| index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_requires_all_characters_in_order() {
+        assert!(fuzzy_score("rct", "/facebook/react").is_some());
+        assert!(fuzzy_score("tcr", "/facebook/react").is_none());
+        assert!(fuzzy_score("xyz", "/facebook/react").is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_score_is_case_insensitive() {
+        assert_eq!(
+            fuzzy_score("NEXT", "next.js"),
+            fuzzy_score("next", "Next.js")
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_consecutive_matches() {
+        let consecutive = fuzzy_score("vue", "/vuejs/core").unwrap();
+        let scattered = fuzzy_score("vue", "/vercel/use-query-e").unwrap();
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_word_boundaries() {
+        let boundary = fuzzy_score("core", "/vuejs/core").unwrap();
+        let inner = fuzzy_score("core", "/x/hardcore").unwrap();
+        assert!(boundary > inner);
+    }
+
+    #[test]
+    fn test_fuzzy_score_ignores_whitespace_in_pattern() {
+        assert!(fuzzy_score("face react", "/facebook/react").is_some());
+    }
+
+    #[test]
+    fn test_fuzzy_filter_empty_pattern_keeps_order() {
+        let candidates = ["/b/lib", "/a/lib", "/c/lib"];
+        assert_eq!(fuzzy_filter("", &candidates), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_fuzzy_filter_orders_by_score_and_drops_misses() {
+        let candidates = ["/sveltejs/svelte", "/vuejs/core", "/facebook/react"];
+        let matched = fuzzy_filter("vue", &candidates);
+        assert_eq!(matched[0], 1);
+        assert!(!matched.contains(&2));
+    }
+}
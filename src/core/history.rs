@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+
+use chrono::SecondsFormat;
+
+use crate::models::HistoryEntry;
+
+/// Library IDs from the history, most recently fetched first.
+///
+/// # Arguments
+/// * `entries` - History entries, oldest first
+///
+/// # Returns
+/// * The latest entry for each distinct ID
+pub fn recent_library_ids(entries: &[HistoryEntry]) -> Vec<&HistoryEntry> {
+    let mut seen = HashSet::new();
+    entries
+        .iter()
+        .rev()
+        .filter(|entry| entry.id.as_ref().is_some_and(|id| seen.insert(id.clone())))
+        .collect()
+}
+
+/// Entries whose query, ID or command contains a term, ignoring case.
+pub fn search_history<'a>(entries: &'a [HistoryEntry], term: &str) -> Vec<&'a HistoryEntry> {
+    let term = term.to_lowercase();
+    entries
+        .iter()
+        .filter(|entry| {
+            [
+                entry.query.as_deref(),
+                entry.id.as_deref(),
+                Some(entry.command.as_str()),
+            ]
+            .into_iter()
+            .flatten()
+            .any(|text| text.to_lowercase().contains(&term))
+        })
+        .collect()
+}
+
+/// Render an entry as one line: timestamp, command, then what was looked up.
+pub fn format_history_entry(entry: &HistoryEntry) -> String {
+    let subject = match (&entry.query, &entry.id) {
+        (Some(query), Some(id)) => format!("{} -> {}", query, id),
+        (Some(query), None) => query.clone(),
+        (None, Some(id)) => id.clone(),
+        (None, None) => String::new(),
+    };
+    format!(
+        "{}  {:<8}  {}",
+        entry.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+        entry.command,
+        subject
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn create_test_entry(command: &str, query: Option<&str>, id: Option<&str>) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: Utc.with_ymd_and_hms(2025, 1, 15, 9, 30, 0).unwrap(),
+            command: command.to_string(),
+            query: query.map(|s| s.to_string()),
+            id: id.map(|s| s.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_recent_library_ids_latest_first_without_duplicates() {
+        let entries = vec![
+            create_test_entry("get-docs", None, Some("/pallets/flask")),
+            create_test_entry("search", Some("react"), None),
+            create_test_entry("get-docs", None, Some("/facebook/react")),
+            create_test_entry("lucky", None, Some("/pallets/flask")),
+        ];
+
+        let recent: Vec<(&str, &str)> = recent_library_ids(&entries)
+            .iter()
+            .map(|entry| (entry.id.as_deref().unwrap(), entry.command.as_str()))
+            .collect();
+
+        assert_eq!(
+            recent,
+            vec![("/pallets/flask", "lucky"), ("/facebook/react", "get-docs")]
+        );
+    }
+
+    #[test]
+    fn test_search_history_ignores_case() {
+        let entries = vec![
+            create_test_entry("search", Some("React hooks"), None),
+            create_test_entry("get-docs", None, Some("/facebook/react")),
+            create_test_entry("get-docs", None, Some("/pallets/flask")),
+        ];
+
+        assert_eq!(search_history(&entries, "REACT").len(), 2);
+        assert_eq!(search_history(&entries, "get-docs").len(), 2);
+        assert!(search_history(&entries, "django").is_empty());
+    }
+
+    #[test]
+    fn test_format_history_entry() {
+        assert_eq!(
+            format_history_entry(&create_test_entry("search", Some("react"), None)),
+            "2025-01-15T09:30:00Z  search    react"
+        );
+        assert_eq!(
+            format_history_entry(&create_test_entry(
+                "resolve",
+                Some("npm:react"),
+                Some("/facebook/react")
+            )),
+            "2025-01-15T09:30:00Z  resolve   npm:react -> /facebook/react"
+        );
+    }
+}
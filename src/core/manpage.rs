@@ -0,0 +1,84 @@
+use anyhow::Result;
+use clap::Command;
+use clap_mangen::Man;
+
+/// A rendered manual page
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManPage {
+    /// Subcommand names leading to the documented command; empty for the top level
+    pub path: Vec<String>,
+    /// File name including the section, e.g. "context7-cli-search.1"
+    pub file_name: String,
+    /// Page contents in roff
+    pub roff: String,
+}
+
+/// Render a manual page for a command and each of its subcommands.
+///
+/// # Arguments
+/// * `command` - The CLI definition
+///
+/// # Returns
+/// * Pages for the command first, then its subcommands depth-first
+pub fn man_pages(command: Command) -> Result<Vec<ManPage>> {
+    let mut command = command.disable_help_subcommand(true);
+    command.build();
+
+    let source = format!("{} {}", command.get_name(), env!("CARGO_PKG_VERSION"));
+    let mut pages = Vec::new();
+    collect_pages(&command, &source, Vec::new(), &mut pages)?;
+    Ok(pages)
+}
+
+fn collect_pages(
+    command: &Command,
+    source: &str,
+    path: Vec<String>,
+    pages: &mut Vec<ManPage>,
+) -> Result<()> {
+    let man = Man::new(command.clone()).source(source);
+    let mut roff = Vec::new();
+    man.render(&mut roff)?;
+    pages.push(ManPage {
+        path: path.clone(),
+        file_name: man.get_filename(),
+        roff: String::from_utf8(roff)?,
+    });
+
+    for subcommand in command.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        let mut sub_path = path.clone();
+        sub_path.push(subcommand.get_name().to_string());
+        collect_pages(subcommand, source, sub_path, pages)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_command() -> Command {
+        Command::new("tool").about("A tool").subcommand(
+            Command::new("config")
+                .about("Configure")
+                .subcommand(Command::new("show").about("Show settings")),
+        )
+    }
+
+    #[test]
+    fn test_man_pages_cover_nested_subcommands() {
+        let pages = man_pages(test_command()).unwrap();
+
+        let names: Vec<&str> = pages.iter().map(|page| page.file_name.as_str()).collect();
+        assert_eq!(names, vec!["tool.1", "tool-config.1", "tool-config-show.1"]);
+        assert_eq!(pages[2].path, vec!["config", "show"]);
+        assert!(pages[2].roff.contains("Show settings"));
+    }
+
+    #[test]
+    fn test_man_pages_skip_help_subcommand() {
+        let pages = man_pages(test_command()).unwrap();
+
+        assert!(!pages.iter().any(|page| page.file_name.contains("help")));
+    }
+}
@@ -0,0 +1,726 @@
+use std::io::IsTerminal;
+
+use crossterm::style::{Color, Colored, ContentStyle, Stylize};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use unicode_width::UnicodeWidthStr;
+
+use crate::core::color::ColorChoice;
+
+/// Width used when the terminal size can't be determined
+const DEFAULT_WIDTH: usize = 80;
+
+/// Narrowest width paragraphs are wrapped to, however small the terminal
+const MIN_WIDTH: usize = 20;
+
+/// Indentation of code block lines
+const CODE_INDENT: &str = "    ";
+
+/// How documentation is written to stdout
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DocsFormat {
+    /// The markdown exactly as fetched
+    #[default]
+    Raw,
+    /// Rendered for reading in a terminal
+    Rendered { width: usize, color: bool },
+}
+
+impl DocsFormat {
+    /// Choose how to print documentation to stdout.
+    ///
+    /// Docs are rendered when stdout is a terminal, or when color is forced
+    /// (e.g. for `| less -R`), unless `raw` asks for the markdown as fetched.
+    ///
+    /// # Arguments
+    /// * `raw` - Whether `--raw` was given
+    /// * `color` - The `--color` setting
+    pub fn for_stdout(raw: bool, color: ColorChoice) -> Self {
+        let is_terminal = std::io::stdout().is_terminal();
+        let color = color.enabled(is_terminal);
+        if raw || !(is_terminal || color) {
+            return DocsFormat::Raw;
+        }
+        if color {
+            // crossterm drops colors when NO_COLOR is set, even for --color always
+            Colored::set_ansi_color_disabled(false);
+        }
+        let width = crossterm::terminal::size()
+            .map(|(columns, _)| columns as usize)
+            .unwrap_or(DEFAULT_WIDTH);
+        DocsFormat::Rendered {
+            width: width.max(MIN_WIDTH),
+            color,
+        }
+    }
+
+    /// Format a documentation body for printing.
+    pub fn format(&self, body: &str) -> String {
+        match self {
+            DocsFormat::Raw => body.to_string(),
+            DocsFormat::Rendered { width, color } => render_markdown(body, *width, *color),
+        }
+    }
+}
+
+/// Render markdown for a terminal.
+///
+/// Headings keep their `#` markers, paragraphs are wrapped to `width` (line
+/// breaks in the source are kept, since docs often list `TITLE:`/`SOURCE:`
+/// lines one per line), lists get bullets and code blocks are indented and
+/// never wrapped.
+///
+/// # Arguments
+/// * `text` - Markdown to render
+/// * `width` - Column to wrap paragraphs at
+/// * `color` - Whether to style headings, emphasis, links and code with ANSI escapes
+///
+/// # Returns
+/// * The rendered text, without a trailing newline
+pub fn render_markdown(text: &str, width: usize, color: bool) -> String {
+    let mut renderer = Renderer::new(width, color);
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    for event in Parser::new_ext(text, options) {
+        renderer.handle(event);
+    }
+    renderer.finish()
+}
+
+/// A run of inline text sharing one style
+#[derive(Debug, Clone)]
+struct Segment {
+    text: String,
+    style: ContentStyle,
+}
+
+/// A table being collected until its end, when column widths are known
+#[derive(Default)]
+struct Table {
+    /// Cells as (plain text, styled text); the first row is the header
+    rows: Vec<Vec<(String, String)>>,
+}
+
+struct Renderer {
+    width: usize,
+    color: bool,
+    lines: Vec<String>,
+    /// Inline text of the block being built
+    segments: Vec<Segment>,
+    styles: Vec<ContentStyle>,
+    quote_depth: usize,
+    /// Enclosing lists, with the next number for ordered ones
+    lists: Vec<Option<u64>>,
+    /// Bullet or number to put before the first line of the current item
+    item_marker: Option<String>,
+    /// Language and text of the code block being collected
+    code: Option<(Option<String>, String)>,
+    /// Destination and first segment of each open link or image
+    links: Vec<(String, usize)>,
+    table: Option<Table>,
+}
+
+impl Renderer {
+    fn new(width: usize, color: bool) -> Self {
+        Self {
+            width,
+            color,
+            lines: Vec::new(),
+            segments: Vec::new(),
+            styles: Vec::new(),
+            quote_depth: 0,
+            lists: Vec::new(),
+            item_marker: None,
+            code: None,
+            links: Vec::new(),
+            table: None,
+        }
+    }
+
+    fn style(&self) -> ContentStyle {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push_style(&mut self, style: ContentStyle) {
+        self.styles.push(style);
+    }
+
+    fn pop_style(&mut self) {
+        self.styles.pop();
+    }
+
+    fn paint(&self, text: &str, style: ContentStyle) -> String {
+        paint(text, style, self.color)
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let style = self.style();
+        self.segments.push(Segment {
+            text: text.to_string(),
+            style,
+        });
+    }
+
+    /// Prefixes for the first and following lines of the current block.
+    fn prefixes(&mut self) -> (String, String) {
+        let quote = "│ ".repeat(self.quote_depth);
+        match self.item_marker.take() {
+            Some(marker) => {
+                let outer = "  ".repeat(self.lists.len().saturating_sub(1));
+                let first = format!("{}{}{}", quote, outer, marker);
+                let rest = format!("{}{}{}", quote, outer, " ".repeat(marker.width()));
+                (first, rest)
+            }
+            None => {
+                let prefix = format!("{}{}", quote, "  ".repeat(self.lists.len()));
+                (prefix.clone(), prefix)
+            }
+        }
+    }
+
+    /// Wrap the inline text collected so far into output lines.
+    fn flush(&mut self) {
+        if self.segments.is_empty() && self.item_marker.is_none() {
+            return;
+        }
+        let (first, rest) = self.prefixes();
+        let segments = std::mem::take(&mut self.segments);
+        self.lines
+            .extend(wrap(&segments, self.width, &first, &rest, self.color));
+    }
+
+    /// Separate the next block from the previous one with an empty line.
+    fn blank(&mut self) {
+        if self.lines.last().is_some_and(|line| !line.is_empty()) {
+            self.lines.push(String::new());
+        }
+    }
+
+    fn handle(&mut self, event: Event) {
+        if let Some((_, code)) = self.code.as_mut() {
+            match event {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => self.end_code_block(),
+                _ => {}
+            }
+            return;
+        }
+
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                self.push_text(&text)
+            }
+            Event::Code(code) => {
+                let segment = if self.color {
+                    Segment {
+                        text: code.to_string(),
+                        style: self.style().with(Color::Yellow),
+                    }
+                } else {
+                    Segment {
+                        text: format!("`{}`", code),
+                        style: self.style(),
+                    }
+                };
+                self.segments.push(segment);
+            }
+            Event::SoftBreak | Event::HardBreak => self.push_text("\n"),
+            Event::Rule => {
+                self.flush();
+                let (prefix, _) = self.prefixes();
+                let rule = "─".repeat(self.width.saturating_sub(prefix.width()));
+                self.lines.push(format!(
+                    "{}{}",
+                    prefix,
+                    self.paint(&rule, ContentStyle::new().dim())
+                ));
+                self.blank();
+            }
+            Event::TaskListMarker(checked) => self.push_text(if checked { "[x] " } else { "[ ] " }),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush();
+                let style = match level {
+                    HeadingLevel::H1 => ContentStyle::new().bold().with(Color::Magenta),
+                    HeadingLevel::H2 => ContentStyle::new().bold().with(Color::Cyan),
+                    _ => ContentStyle::new().bold(),
+                };
+                self.push_style(style);
+                self.push_text(&format!("{} ", "#".repeat(level as usize)));
+            }
+            Tag::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(kind) => {
+                self.flush();
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().map(str::to_string)
+                    }
+                    CodeBlockKind::Indented => None,
+                };
+                self.code = Some((language, String::new()));
+            }
+            Tag::HtmlBlock => self.flush(),
+            Tag::List(start) => {
+                // A nested list follows the text of its parent item
+                self.flush();
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.item_marker = Some(marker);
+            }
+            Tag::Emphasis => self.push_style(self.style().italic()),
+            Tag::Strong => self.push_style(self.style().bold()),
+            Tag::Strikethrough => self.push_style(self.style().crossed_out()),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.links.push((dest_url.to_string(), self.segments.len()));
+                self.push_style(self.style().underlined().with(Color::Blue));
+            }
+            Tag::Table(_) => {
+                self.flush();
+                self.table = Some(Table::default());
+            }
+            Tag::TableHead | Tag::TableRow => {
+                if let Some(table) = self.table.as_mut() {
+                    table.rows.push(Vec::new());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::HtmlBlock => {
+                self.flush();
+                self.blank();
+            }
+            TagEnd::Heading(_) => {
+                self.flush();
+                self.pop_style();
+                self.blank();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.blank();
+            }
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank();
+                }
+            }
+            TagEnd::Item => self.flush(),
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => self.pop_style(),
+            TagEnd::Link | TagEnd::Image => {
+                self.pop_style();
+                if let Some((url, start)) = self.links.pop() {
+                    let label: String = self.segments[start..]
+                        .iter()
+                        .map(|segment| segment.text.as_str())
+                        .collect();
+                    if !url.is_empty() && label != url {
+                        self.segments.push(Segment {
+                            text: format!(" ({})", url),
+                            style: self.style().dim(),
+                        });
+                    }
+                }
+            }
+            TagEnd::TableCell => {
+                let segments = std::mem::take(&mut self.segments);
+                let plain: String = segments.iter().map(|s| s.text.as_str()).collect();
+                let styled: String = segments
+                    .iter()
+                    .map(|s| self.paint(&s.text, s.style))
+                    .collect();
+                if let Some(row) = self.table.as_mut().and_then(|table| table.rows.last_mut()) {
+                    row.push((plain, styled));
+                }
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.render_table(table);
+                }
+                self.blank();
+            }
+            _ => {}
+        }
+    }
+
+    fn end_code_block(&mut self) {
+        let Some((language, code)) = self.code.take() else {
+            return;
+        };
+        let (prefix, _) = self.prefixes();
+        for line in code.lines() {
+            let line = if self.color {
+                highlight_code(line, language.as_deref())
+            } else {
+                line.to_string()
+            };
+            self.lines.push(
+                format!("{}{}{}", prefix, CODE_INDENT, line)
+                    .trim_end()
+                    .to_string(),
+            );
+        }
+        self.blank();
+    }
+
+    fn render_table(&mut self, table: Table) {
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns)
+            .map(|column| {
+                table
+                    .rows
+                    .iter()
+                    .filter_map(|row| row.get(column))
+                    .map(|(plain, _)| plain.width())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let (prefix, _) = self.prefixes();
+
+        for (index, row) in table.rows.iter().enumerate() {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|((plain, styled), width)| {
+                    let styled = if index == 0 {
+                        self.paint(plain, ContentStyle::new().bold())
+                    } else {
+                        styled.clone()
+                    };
+                    format!("{}{}", styled, " ".repeat(width - plain.width()))
+                })
+                .collect();
+            self.lines.push(
+                format!("{}{}", prefix, cells.join(" │ "))
+                    .trim_end()
+                    .to_string(),
+            );
+            if index == 0 {
+                let rule: Vec<String> = widths.iter().map(|width| "─".repeat(*width)).collect();
+                self.lines.push(format!("{}{}", prefix, rule.join("─┼─")));
+            }
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        while self.lines.last().is_some_and(|line| line.is_empty()) {
+            self.lines.pop();
+        }
+        self.lines.join("\n")
+    }
+}
+
+fn paint(text: &str, style: ContentStyle, color: bool) -> String {
+    if color && style != ContentStyle::new() {
+        style.apply(text).to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Fill lines up to `width` columns with the words of the segments.
+///
+/// Newlines in the text always start a new line. A word longer than the
+/// width gets a line of its own rather than being split.
+fn wrap(segments: &[Segment], width: usize, first: &str, rest: &str, color: bool) -> Vec<String> {
+    let mut filler = LineFiller {
+        width,
+        rest,
+        color,
+        lines: Vec::new(),
+        line: first.to_string(),
+        line_width: first.width(),
+        has_content: false,
+        pending_space: false,
+    };
+    let mut word: Vec<(String, ContentStyle)> = Vec::new();
+
+    for segment in segments {
+        for ch in segment.text.chars() {
+            if ch == '\n' {
+                filler.add_word(&mut word);
+                filler.break_line();
+            } else if ch.is_whitespace() {
+                filler.add_word(&mut word);
+                filler.pending_space = true;
+            } else {
+                match word.last_mut() {
+                    Some((text, style)) if *style == segment.style => text.push(ch),
+                    _ => word.push((ch.to_string(), segment.style)),
+                }
+            }
+        }
+    }
+    filler.add_word(&mut word);
+    filler.finish()
+}
+
+/// Lines being filled by `wrap`
+struct LineFiller<'a> {
+    width: usize,
+    /// Prefix of every line after the first
+    rest: &'a str,
+    color: bool,
+    lines: Vec<String>,
+    line: String,
+    line_width: usize,
+    has_content: bool,
+    /// Whether whitespace came between the last word and the next one
+    pending_space: bool,
+}
+
+impl LineFiller<'_> {
+    fn add_word(&mut self, word: &mut Vec<(String, ContentStyle)>) {
+        if word.is_empty() {
+            return;
+        }
+        let word_width: usize = word.iter().map(|(text, _)| text.width()).sum();
+        let space = usize::from(self.has_content && self.pending_space);
+        if self.has_content && self.line_width + space + word_width > self.width {
+            self.break_line();
+        } else if space == 1 {
+            self.line.push(' ');
+            self.line_width += 1;
+        }
+        for (text, style) in word.drain(..) {
+            self.line.push_str(&paint(&text, style, self.color));
+        }
+        self.line_width += word_width;
+        self.has_content = true;
+        self.pending_space = false;
+    }
+
+    fn break_line(&mut self) {
+        let line = std::mem::replace(&mut self.line, self.rest.to_string());
+        self.lines.push(line.trim_end().to_string());
+        self.line_width = self.rest.width();
+        self.has_content = false;
+        self.pending_space = false;
+    }
+
+    fn finish(mut self) -> Vec<String> {
+        if self.has_content || self.lines.is_empty() {
+            self.break_line();
+        }
+        self.lines
+    }
+}
+
+/// How one family of languages marks line comments and strings
+struct Syntax {
+    comment: &'static str,
+    /// Whether `'` quotes strings rather than chars and lifetimes
+    single_quote_strings: bool,
+}
+
+impl Syntax {
+    fn for_language(language: Option<&str>) -> Syntax {
+        match language.map(str::to_lowercase).as_deref() {
+            Some("rust" | "rs") => Syntax {
+                comment: "//",
+                single_quote_strings: false,
+            },
+            Some(
+                "python" | "py" | "sh" | "bash" | "shell" | "zsh" | "console" | "shellscript"
+                | "toml" | "yaml" | "yml" | "ini" | "dockerfile",
+            ) => Syntax {
+                comment: "#",
+                single_quote_strings: true,
+            },
+            _ => Syntax {
+                comment: "//",
+                single_quote_strings: true,
+            },
+        }
+    }
+}
+
+/// Color strings and line comments in one line of code.
+///
+/// This is a lexical approximation that works line by line, so strings and
+/// comments spanning lines are only colored on their first line.
+fn highlight_code(line: &str, language: Option<&str>) -> String {
+    let syntax = Syntax::for_language(language);
+    let mut out = String::new();
+    let mut rest = line;
+
+    while let Some(ch) = rest.chars().next() {
+        if rest.starts_with(syntax.comment) {
+            out.push_str(&rest.with(Color::DarkGrey).to_string());
+            break;
+        }
+        let quotes_string = match ch {
+            '"' | '`' => true,
+            '\'' => syntax.single_quote_strings,
+            _ => false,
+        };
+        let token_len = if quotes_string {
+            rest[1..].find(ch).map(|end| end + 2)
+        } else {
+            None
+        };
+        if let Some(len) = token_len {
+            out.push_str(&rest[..len].with(Color::Green).to_string());
+            rest = &rest[len..];
+            continue;
+        }
+        out.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(text: &str, width: usize) -> String {
+        render_markdown(text, width, false)
+    }
+
+    #[test]
+    fn test_render_wraps_paragraphs_and_keeps_headings() {
+        let output = plain("# Title\n\nOne two three four five six", 14);
+
+        assert_eq!(output, "# Title\n\nOne two three\nfour five six");
+    }
+
+    #[test]
+    fn test_render_keeps_source_line_breaks() {
+        let output = plain("TITLE: Install\nSOURCE: https://example.com", 80);
+
+        assert_eq!(output, "TITLE: Install\nSOURCE: https://example.com");
+    }
+
+    #[test]
+    fn test_render_lists_with_markers_and_hanging_indent() {
+        let output = plain("- alpha beta gamma\n  - nested\n\n1. first\n2. second", 12);
+
+        assert_eq!(
+            output,
+            "• alpha beta\n  gamma\n  • nested\n\n1. first\n2. second"
+        );
+    }
+
+    #[test]
+    fn test_render_indents_code_blocks_without_wrapping() {
+        let output = plain(
+            "Run:\n\n```sh\nnpm install --save some-long-package-name\n```",
+            20,
+        );
+
+        assert_eq!(
+            output,
+            "Run:\n\n    npm install --save some-long-package-name"
+        );
+    }
+
+    #[test]
+    fn test_render_inline_code_links_and_rules() {
+        let output = plain("Call `init()` see [docs](https://d.dev)\n\n---\n\nend", 40);
+
+        assert_eq!(
+            output,
+            format!(
+                "Call `init()` see docs (https://d.dev)\n\n{}\n\nend",
+                "─".repeat(40)
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_tables_aligns_columns() {
+        let output = plain("| Name | Kind |\n|---|---|\n| a | longer |", 40);
+
+        assert_eq!(output, "Name │ Kind\n─────┼───────\na    │ longer");
+    }
+
+    #[test]
+    fn test_render_block_quotes() {
+        let output = plain("> quoted text", 40);
+
+        assert_eq!(output, "│ quoted text");
+    }
+
+    #[test]
+    fn test_render_without_color_has_no_escapes() {
+        let output = plain("# T\n\n**bold** _it_\n\n```rust\nfn main() {}\n```", 40);
+
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_render_with_color_styles_headings_and_code() {
+        let output = render_markdown("# Title\n\n```rust\nfn main() {} // hi\n```", 40, true);
+
+        assert!(output.contains('\x1b'));
+        assert!(output.contains(&"Title".bold().with(Color::Magenta).to_string()));
+        assert!(output.contains(&"// hi".with(Color::DarkGrey).to_string()));
+    }
+
+    #[test]
+    fn test_highlight_code_strings_and_comments() {
+        let line = highlight_code("let x = \"hi\" + 42; // note", Some("rust"));
+
+        assert!(line.starts_with("let x = "));
+        assert!(line.contains(&"\"hi\"".with(Color::Green).to_string()));
+        assert!(line.contains(" + 42; "));
+        assert!(line.ends_with(&"// note".with(Color::DarkGrey).to_string()));
+    }
+
+    #[test]
+    fn test_highlight_code_rust_lifetimes_are_not_strings() {
+        let line = highlight_code("fn f<'a>(x: &'a str)", Some("rust"));
+
+        assert_eq!(line, "fn f<'a>(x: &'a str)");
+    }
+
+    #[test]
+    fn test_highlight_code_uses_language_comment_marker() {
+        let python = highlight_code("# comment", Some("python"));
+        let rust = highlight_code("# not a comment", Some("rust"));
+
+        assert_eq!(python, "# comment".with(Color::DarkGrey).to_string());
+        assert!(rust.starts_with("# not"));
+    }
+
+    #[test]
+    fn test_docs_format_raw_leaves_body_unchanged() {
+        let body = "# Title\n\nsome   text";
+
+        assert_eq!(DocsFormat::Raw.format(body), body);
+        assert_eq!(
+            DocsFormat::Rendered {
+                width: 80,
+                color: false
+            }
+            .format(body),
+            "# Title\n\nsome text"
+        );
+    }
+}
@@ -1,3 +1,19 @@
+pub mod alias;
+pub mod color;
+pub mod completion;
+pub mod config;
+pub mod dates;
+pub mod docs;
+pub mod fields;
+pub mod filtering;
 pub mod formatting;
+pub mod fuzzy;
+pub mod history;
+pub mod manpage;
+pub mod markdown;
+pub mod repl;
+pub mod resolve;
+pub mod schema;
+pub mod scoring;
 pub mod sorting;
 pub mod validation;
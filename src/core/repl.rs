@@ -0,0 +1,418 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+
+use crate::core::alias::expand_alias;
+use crate::core::scoring::{ScoreWeights, rank_by_relevance};
+use crate::core::sorting::{SortSpec, apply_limit, sort_search_results_by};
+use crate::models::LibraryId;
+use crate::models::search::SearchResult;
+
+/// Sort key that orders results by relevance to the query instead of a field
+const RELEVANCE_SORT: &str = "relevance";
+
+/// Base URL of a library's page on the Context7 website
+const LIBRARY_PAGE_URL: &str = "https://context7.com";
+
+/// Commands understood by the shell, in the order `help` lists them
+pub const SHELL_COMMANDS: [&str; 10] = [
+    "search", "docs", "lucky", "open", "sort", "limit", "history", "help", "exit", "quit",
+];
+
+/// Usage shown by the shell's `help` command
+pub const SHELL_HELP: &str = "\
+search <query>        Search for libraries and list the results
+docs <n|id|alias>     Print documentation for result n, a library ID or an alias
+lucky <query>         Print documentation for the most relevant result
+open <n|id|alias>     Open a library's Context7 page in the browser
+sort [fields]         Show or change the sort order (e.g. stars, -trustScore, relevance)
+limit [n|none]        Show or change how many results are listed
+history               Show the lines entered in this and earlier sessions
+help                  Show this help
+exit, quit            Leave the shell (or press Ctrl-D)";
+
+/// A parsed line of shell input
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShellCommand {
+    Search(String),
+    Docs(String),
+    Lucky(String),
+    Open(String),
+    Sort(Option<String>),
+    Limit(Option<String>),
+    History,
+    Help,
+    Exit,
+}
+
+impl ShellCommand {
+    /// Parse a line of shell input.
+    ///
+    /// # Returns
+    /// * `Ok(Some(command))` - For a valid command
+    /// * `Ok(None)` - For a blank line
+    /// * `Err` - For an unknown command or a missing argument
+    pub fn parse(line: &str) -> Result<Option<Self>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+        let (name, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        let argument = || -> Result<String> {
+            if rest.is_empty() {
+                anyhow::bail!("'{}' needs an argument. Type 'help' for usage", name);
+            }
+            Ok(rest.to_string())
+        };
+        let optional = (!rest.is_empty()).then(|| rest.to_string());
+
+        let command = match name {
+            "search" => ShellCommand::Search(argument()?),
+            "docs" => ShellCommand::Docs(argument()?),
+            "lucky" => ShellCommand::Lucky(argument()?),
+            "open" => ShellCommand::Open(argument()?),
+            "sort" => ShellCommand::Sort(optional),
+            "limit" => ShellCommand::Limit(optional),
+            "history" => ShellCommand::History,
+            "help" | "?" => ShellCommand::Help,
+            "exit" | "quit" => ShellCommand::Exit,
+            other => anyhow::bail!(
+                "Unknown command '{}'. Valid options are: {}",
+                other,
+                SHELL_COMMANDS.join(", ")
+            ),
+        };
+        Ok(Some(command))
+    }
+}
+
+/// What the shell remembers between commands
+#[derive(Debug, Clone)]
+pub struct ShellState {
+    query: Option<String>,
+    results: Vec<SearchResult>,
+    sort_by: String,
+    limit: Option<usize>,
+}
+
+impl ShellState {
+    /// Create an empty state.
+    ///
+    /// # Arguments
+    /// * `sort_by` - Initial sort order, as accepted by `search --sort-by`
+    /// * `limit` - Initial number of results to list
+    pub fn new(sort_by: String, limit: Option<usize>) -> Result<Self> {
+        let mut state = Self {
+            query: None,
+            results: Vec::new(),
+            sort_by: String::new(),
+            limit,
+        };
+        state.set_sort(&sort_by)?;
+        Ok(state)
+    }
+
+    /// Replace the last result set with a new search's results.
+    pub fn set_results(&mut self, query: &str, results: Vec<SearchResult>) {
+        self.query = Some(query.to_string());
+        self.results = results;
+    }
+
+    /// The last results in the current sort order, cut to the current limit.
+    pub fn listed(&self) -> Vec<SearchResult> {
+        let results = self.results.clone();
+        let ordered = if self.sort_by == RELEVANCE_SORT {
+            let query = self.query.as_deref().unwrap_or("");
+            rank_by_relevance(query, results, &ScoreWeights::default())
+                .into_iter()
+                .map(|scored| scored.result)
+                .collect()
+        } else {
+            // Validated by set_sort
+            match SortSpec::from_str(&self.sort_by) {
+                Ok(spec) => sort_search_results_by(results, &spec),
+                Err(_) => results,
+            }
+        };
+        match self.limit {
+            Some(limit) => apply_limit(ordered, limit),
+            None => ordered,
+        }
+    }
+
+    pub fn sort_by(&self) -> &str {
+        &self.sort_by
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Change the sort order, keeping the old one if the new one is invalid.
+    pub fn set_sort(&mut self, sort_by: &str) -> Result<()> {
+        if sort_by != RELEVANCE_SORT {
+            SortSpec::from_str(sort_by)?;
+        }
+        self.sort_by = sort_by.to_string();
+        Ok(())
+    }
+
+    /// Change the limit; "none" or "0" lists every result.
+    pub fn set_limit(&mut self, limit: &str) -> Result<()> {
+        self.limit = match limit {
+            "none" | "0" => None,
+            _ => Some(limit.parse().map_err(|_| {
+                anyhow::anyhow!("Invalid limit '{}'. Expected a number or 'none'", limit)
+            })?),
+        };
+        Ok(())
+    }
+
+    /// Resolve a `docs` or `open` argument to a library ID.
+    ///
+    /// # Arguments
+    /// * `target` - A 1-based position in the listed results, a library ID or URL, or an alias
+    /// * `aliases` - Aliases from the config file
+    pub fn target(&self, target: &str, aliases: &BTreeMap<String, String>) -> Result<LibraryId> {
+        if let Ok(position) = target.parse::<usize>() {
+            let listed = self.listed();
+            return match position.checked_sub(1).and_then(|index| listed.get(index)) {
+                Some(result) => LibraryId::parse(&result.id),
+                None if listed.is_empty() => {
+                    anyhow::bail!("No results to pick from. Run 'search <query>' first")
+                }
+                None => anyhow::bail!(
+                    "No result {}. Pick a number from 1 to {}",
+                    position,
+                    listed.len()
+                ),
+            };
+        }
+        LibraryId::parse(&expand_alias(target, aliases))
+    }
+
+    /// Words offered by tab completion after `docs` and `open`: listed IDs.
+    pub fn listed_ids(&self) -> Vec<String> {
+        self.listed().into_iter().map(|result| result.id).collect()
+    }
+}
+
+/// Render results as a numbered list for picking with `docs <n>`.
+///
+/// # Arguments
+/// * `results` - Results to list
+/// * `aliases` - Alias labels keyed by result ID, shown after the results they point at
+pub fn format_listing(results: &[SearchResult], aliases: &HashMap<String, String>) -> String {
+    let width = results.len().to_string().len();
+    results
+        .iter()
+        .enumerate()
+        .map(|(index, result)| {
+            let stars = result
+                .stars
+                .map(|stars| format!("  ★{}", stars))
+                .unwrap_or_default();
+            let alias = aliases
+                .get(&result.id)
+                .map(|alias| format!("  ({})", alias))
+                .unwrap_or_default();
+            format!(
+                "{:>width$}. {}  {}{}{}",
+                index + 1,
+                result.id,
+                result.title.as_deref().unwrap_or(""),
+                stars,
+                alias,
+                width = width
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Complete the word before the cursor.
+///
+/// The first word completes to a command name; the argument of `docs` and
+/// `open` completes to one of `targets`.
+///
+/// # Arguments
+/// * `line` - Text before the cursor
+/// * `targets` - Library IDs and aliases to offer as arguments
+///
+/// # Returns
+/// * Byte offset where the completed word starts, and the matching words
+pub fn complete_shell_line(line: &str, targets: &[String]) -> (usize, Vec<String>) {
+    let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+    let word = &line[start..];
+    let preceding: Vec<&str> = line[..start].split_whitespace().collect();
+
+    let candidates: Vec<String> = match preceding.as_slice() {
+        [] => SHELL_COMMANDS.iter().map(|c| c.to_string()).collect(),
+        ["docs" | "open"] => targets.to_vec(),
+        _ => Vec::new(),
+    };
+    (
+        start,
+        candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .collect(),
+    )
+}
+
+/// URL of a library's page on the Context7 website.
+pub fn library_page_url(id: &LibraryId) -> String {
+    format!("{}/{}", LIBRARY_PAGE_URL, id.as_path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_result(id: &str, title: &str, stars: i64) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            stars: Some(stars),
+            title: Some(title.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn test_state() -> ShellState {
+        let mut state = ShellState::new("stars".to_string(), None).unwrap();
+        state.set_results(
+            "flask",
+            vec![
+                create_test_result("/small/flask-extras", "Flask Extras", 10),
+                create_test_result("/pallets/flask", "Flask", 65000),
+            ],
+        );
+        state
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            ShellCommand::parse("  search  react hooks ").unwrap(),
+            Some(ShellCommand::Search("react hooks".to_string()))
+        );
+        assert_eq!(
+            ShellCommand::parse("docs 2").unwrap(),
+            Some(ShellCommand::Docs("2".to_string()))
+        );
+        assert_eq!(
+            ShellCommand::parse("sort").unwrap(),
+            Some(ShellCommand::Sort(None))
+        );
+        assert_eq!(
+            ShellCommand::parse("quit").unwrap(),
+            Some(ShellCommand::Exit)
+        );
+        assert_eq!(ShellCommand::parse("   ").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error_msg = ShellCommand::parse("docs").unwrap_err().to_string();
+        assert!(error_msg.contains("'docs' needs an argument"));
+
+        let error_msg = ShellCommand::parse("fetch x").unwrap_err().to_string();
+        assert!(error_msg.contains("Unknown command 'fetch'"));
+    }
+
+    #[test]
+    fn test_listed_follows_sort_and_limit() {
+        let mut state = test_state();
+        assert_eq!(
+            state.listed_ids(),
+            vec!["/pallets/flask", "/small/flask-extras"]
+        );
+
+        state.set_sort("id").unwrap();
+        assert_eq!(state.listed_ids()[0], "/pallets/flask");
+        state.set_sort("-id").unwrap();
+        assert_eq!(state.listed_ids()[0], "/small/flask-extras");
+
+        state.set_sort("relevance").unwrap();
+        assert_eq!(state.listed_ids()[0], "/pallets/flask");
+
+        state.set_limit("1").unwrap();
+        assert_eq!(state.listed().len(), 1);
+        state.set_limit("none").unwrap();
+        assert_eq!(state.listed().len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_sort_and_limit_keep_previous_values() {
+        let mut state = test_state();
+
+        assert!(state.set_sort("popularity").is_err());
+        assert_eq!(state.sort_by(), "stars");
+        assert!(state.set_limit("many").is_err());
+        assert_eq!(state.limit(), None);
+    }
+
+    #[test]
+    fn test_target_by_position_id_or_alias() {
+        let state = test_state();
+        let aliases = BTreeMap::from([("fl".to_string(), "/pallets/flask".to_string())]);
+
+        assert_eq!(
+            state.target("2", &aliases).unwrap().to_string(),
+            "/small/flask-extras"
+        );
+        assert_eq!(
+            state.target("fl", &aliases).unwrap().to_string(),
+            "/pallets/flask"
+        );
+        assert_eq!(
+            state
+                .target("tokio-rs/tokio", &aliases)
+                .unwrap()
+                .to_string(),
+            "/tokio-rs/tokio"
+        );
+
+        let error_msg = state.target("3", &aliases).unwrap_err().to_string();
+        assert!(error_msg.contains("Pick a number from 1 to 2"));
+        let empty = ShellState::new("stars".to_string(), None).unwrap();
+        assert!(empty.target("1", &aliases).is_err());
+    }
+
+    #[test]
+    fn test_format_listing() {
+        let results = test_state().listed();
+        let aliases = HashMap::from([("/pallets/flask".to_string(), "fl".to_string())]);
+
+        assert_eq!(
+            format_listing(&results, &aliases),
+            "1. /pallets/flask  Flask  ★65000  (fl)\n2. /small/flask-extras  Flask Extras  ★10"
+        );
+    }
+
+    #[test]
+    fn test_complete_shell_line() {
+        let targets = vec!["/pallets/flask".to_string(), "fl".to_string()];
+
+        assert_eq!(
+            complete_shell_line("se", &targets),
+            (0, vec!["search".to_string()])
+        );
+        assert_eq!(
+            complete_shell_line("docs /pa", &targets),
+            (5, vec!["/pallets/flask".to_string()])
+        );
+        assert_eq!(complete_shell_line("open f", &targets).1, vec!["fl"]);
+        assert!(complete_shell_line("search fl", &targets).1.is_empty());
+    }
+
+    #[test]
+    fn test_library_page_url() {
+        let id = LibraryId::parse("/vercel/next.js/v14.3.0").unwrap();
+        assert_eq!(
+            library_page_url(&id),
+            "https://context7.com/vercel/next.js/v14.3.0"
+        );
+    }
+}
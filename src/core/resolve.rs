@@ -0,0 +1,267 @@
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::core::scoring::{ScoreWeights, ScoredResult};
+use crate::models::LibraryId;
+
+/// Package registry prefixes accepted in front of a package name (e.g., "npm:react")
+const REGISTRY_PREFIXES: [&str; 6] = ["npm:", "crates:", "crate:", "cargo:", "pypi:", "pip:"];
+
+/// Package registry URL prefixes whose next path segment is the package name
+const REGISTRY_URLS: [&str; 4] = [
+    "npmjs.com/package/",
+    "crates.io/crates/",
+    "pypi.org/project/",
+    "docs.rs/",
+];
+
+/// Output format for resolved IDs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResolveFormat {
+    Text,
+    Json,
+}
+
+impl ResolveFormat {
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(ResolveFormat::Text),
+            "json" => Ok(ResolveFormat::Json),
+            _ => anyhow::bail!(
+                "Invalid resolve format '{}'. Valid options are: text, json",
+                s
+            ),
+        }
+    }
+}
+
+/// How an input was mapped to a library ID
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ResolveSource {
+    /// The input was already a library ID or a Context7/GitHub URL
+    Direct,
+    /// The input was searched for and the best candidate chosen
+    Search,
+}
+
+/// The library ID an input resolved to, and how sure we are about it
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Resolution {
+    pub input: String,
+    pub id: String,
+    pub title: Option<String>,
+    /// 1.0 for direct mappings; otherwise the top candidate's share of the best possible score
+    pub confidence: f64,
+    pub source: ResolveSource,
+}
+
+impl Resolution {
+    /// Resolve an input without searching, if it is already an ID or supported URL.
+    pub fn direct(input: &str) -> Option<Self> {
+        LibraryId::from_query(input).map(|id| Resolution {
+            input: input.to_string(),
+            id: id.to_string(),
+            title: None,
+            confidence: 1.0,
+            source: ResolveSource::Direct,
+        })
+    }
+
+    /// Pick the best ranked search candidate for an input.
+    ///
+    /// # Arguments
+    /// * `input` - What the user asked to resolve
+    /// * `ranked` - Search results ranked by relevance, best first
+    /// * `weights` - Weights the results were ranked with
+    ///
+    /// # Returns
+    /// * `Some(Resolution)` - For the top candidate
+    /// * `None` - If there were no candidates
+    pub fn from_ranked(
+        input: &str,
+        ranked: &[ScoredResult],
+        weights: &ScoreWeights,
+    ) -> Option<Self> {
+        let top = ranked.first()?;
+        let max_total = weights.name + weights.trust + weights.stars + weights.snippets;
+        let confidence = if max_total > 0.0 {
+            (top.score.total / max_total).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        Some(Resolution {
+            input: input.to_string(),
+            id: top.result.id.clone(),
+            title: top.result.title.clone(),
+            confidence,
+            source: ResolveSource::Search,
+        })
+    }
+}
+
+/// Reduce a package reference to the name worth searching for.
+///
+/// Strips registry prefixes ("npm:react"), registry URLs
+/// ("https://crates.io/crates/serde"), npm scopes ("@tanstack/react-query")
+/// and version requirements ("serde@1.0", "requests==2.31").
+pub fn package_search_term(input: &str) -> String {
+    let mut term = input.trim();
+
+    let without_scheme = term
+        .strip_prefix("https://")
+        .or_else(|| term.strip_prefix("http://"))
+        .unwrap_or(term);
+    let without_www = without_scheme
+        .strip_prefix("www.")
+        .unwrap_or(without_scheme);
+    for url in REGISTRY_URLS {
+        if let Some(rest) = without_www.strip_prefix(url) {
+            term = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+            break;
+        }
+    }
+
+    for prefix in REGISTRY_PREFIXES {
+        if let Some(rest) = term.strip_prefix(prefix) {
+            term = rest;
+            break;
+        }
+    }
+
+    // "@scope/name" searches better as just "name"
+    if let Some(scoped) = term.strip_prefix('@') {
+        term = scoped.split_once('/').map_or(scoped, |(_, name)| name);
+    }
+
+    // "name@1.2" or "name==1.2"
+    let term = term
+        .split(['@', '=', '<', '>', '~', '^'])
+        .next()
+        .unwrap_or(term);
+    term.trim().to_string()
+}
+
+/// Render a resolution as a single line: the ID followed by its confidence.
+pub fn format_resolution_text(resolution: &Resolution) -> String {
+    format!("{}\t{:.2}", resolution.id, resolution.confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::scoring::rank_by_relevance;
+    use crate::models::search::SearchResult;
+
+    fn create_test_result(id: &str, title: &str, stars: i64) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            title: Some(title.to_string()),
+            total_snippets: Some(1000),
+            stars: Some(stars),
+            trust_score: Some(9.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_format_from_str() {
+        assert_eq!(
+            ResolveFormat::from_str("JSON").unwrap(),
+            ResolveFormat::Json
+        );
+        assert_eq!(
+            ResolveFormat::from_str("text").unwrap(),
+            ResolveFormat::Text
+        );
+        let error_msg = ResolveFormat::from_str("yaml").unwrap_err().to_string();
+        assert!(error_msg.contains("Valid options are: text, json"));
+    }
+
+    #[test]
+    fn test_direct_github_url() {
+        let resolution = Resolution::direct("https://github.com/tokio-rs/tokio").unwrap();
+        assert_eq!(resolution.id, "/tokio-rs/tokio");
+        assert_eq!(resolution.confidence, 1.0);
+        assert_eq!(resolution.source, ResolveSource::Direct);
+    }
+
+    #[test]
+    fn test_direct_ignores_package_names() {
+        assert!(Resolution::direct("npm:react").is_none());
+        assert!(Resolution::direct("serde").is_none());
+        assert!(Resolution::direct("https://crates.io/crates/serde").is_none());
+    }
+
+    #[test]
+    fn test_package_search_term() {
+        assert_eq!(package_search_term("npm:react"), "react");
+        assert_eq!(package_search_term("crates:serde"), "serde");
+        assert_eq!(package_search_term("pypi:requests==2.31"), "requests");
+        assert_eq!(package_search_term("@tanstack/react-query"), "react-query");
+        assert_eq!(
+            package_search_term("npm:@tanstack/react-query@5"),
+            "react-query"
+        );
+        assert_eq!(
+            package_search_term("https://crates.io/crates/serde"),
+            "serde"
+        );
+        assert_eq!(
+            package_search_term("https://www.npmjs.com/package/express?activeTab=readme"),
+            "express"
+        );
+        assert_eq!(
+            package_search_term("https://pypi.org/project/flask/"),
+            "flask"
+        );
+        assert_eq!(package_search_term("  tokio "), "tokio");
+    }
+
+    #[test]
+    fn test_from_ranked_picks_top_candidate() {
+        let weights = ScoreWeights::default();
+        let ranked = rank_by_relevance(
+            "serde",
+            vec![
+                create_test_result("/serde-rs/json", "Serde JSON", 5000),
+                create_test_result("/serde-rs/serde", "Serde", 9000),
+            ],
+            &weights,
+        );
+
+        let resolution = Resolution::from_ranked("crates:serde", &ranked, &weights).unwrap();
+
+        assert_eq!(resolution.id, "/serde-rs/serde");
+        assert_eq!(resolution.input, "crates:serde");
+        assert_eq!(resolution.source, ResolveSource::Search);
+        assert!(resolution.confidence > 0.5 && resolution.confidence <= 1.0);
+    }
+
+    #[test]
+    fn test_from_ranked_confidence_is_lower_for_weak_matches() {
+        let weights = ScoreWeights::default();
+        let candidates = vec![create_test_result("/serde-rs/serde", "Serde", 9000)];
+
+        let strong = rank_by_relevance("serde", candidates.clone(), &weights);
+        let weak = rank_by_relevance("json parser", candidates, &weights);
+
+        let strong = Resolution::from_ranked("serde", &strong, &weights).unwrap();
+        let weak = Resolution::from_ranked("json parser", &weak, &weights).unwrap();
+        assert!(strong.confidence > weak.confidence);
+    }
+
+    #[test]
+    fn test_from_ranked_empty() {
+        assert!(Resolution::from_ranked("x", &[], &ScoreWeights::default()).is_none());
+    }
+
+    #[test]
+    fn test_format_resolution_text() {
+        let resolution = Resolution::direct("/pallets/flask").unwrap();
+        assert_eq!(format_resolution_text(&resolution), "/pallets/flask\t1.00");
+    }
+}
@@ -0,0 +1,369 @@
+use anyhow::Result;
+use schemars::schema_for;
+use serde_json::{Value, json};
+
+use crate::core::docs::LibraryDocs;
+use crate::core::resolve::Resolution;
+use crate::models::SearchEnvelope;
+use crate::models::search::SearchResult;
+
+/// Machine-readable outputs that have a published JSON Schema.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaTarget {
+    /// `search --format json`: an array of results
+    Search,
+    /// A single search result, as printed on each line of `search --format ndjson`
+    SearchResult,
+    /// `search --format json-envelope`
+    JsonEnvelope,
+    /// `resolve --format json`
+    Resolve,
+    /// `get-docs --json`
+    Docs,
+}
+
+impl SchemaTarget {
+    pub const ALL: [SchemaTarget; 5] = [
+        SchemaTarget::Search,
+        SchemaTarget::SearchResult,
+        SchemaTarget::JsonEnvelope,
+        SchemaTarget::Resolve,
+        SchemaTarget::Docs,
+    ];
+
+    pub fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|target| target.name() == s)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid schema '{}'. Valid options are: {}",
+                    s,
+                    Self::ALL.map(|target| target.name()).join(", ")
+                )
+            })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchemaTarget::Search => "search",
+            SchemaTarget::SearchResult => "search-result",
+            SchemaTarget::JsonEnvelope => "json-envelope",
+            SchemaTarget::Resolve => "resolve",
+            SchemaTarget::Docs => "docs",
+        }
+    }
+
+    /// Whether this output can be narrowed with `search --fields`
+    pub fn supports_fields(&self) -> bool {
+        matches!(
+            self,
+            SchemaTarget::Search | SchemaTarget::SearchResult | SchemaTarget::JsonEnvelope
+        )
+    }
+}
+
+/// Generate the JSON Schema for an output from the types that produce it.
+///
+/// # Arguments
+/// * `target` - Output to describe
+///
+/// # Returns
+/// * JSON Schema (draft 2020-12) as a JSON value
+pub fn schema_for_target(target: SchemaTarget) -> Value {
+    let schema = match target {
+        SchemaTarget::Search => schema_for!(Vec<SearchResult>),
+        SchemaTarget::SearchResult => schema_for!(SearchResult),
+        SchemaTarget::JsonEnvelope => schema_for!(SearchEnvelope),
+        SchemaTarget::Resolve => schema_for!(Resolution),
+        SchemaTarget::Docs => schema_for!(LibraryDocs),
+    };
+    schema.to_value()
+}
+
+/// Generate the JSON Schema for an output narrowed with `--fields`.
+///
+/// Projected results keep only the selected properties, so none of them are
+/// required, and they may carry an `alias` property that full results don't.
+///
+/// # Arguments
+/// * `target` - Output to describe
+///
+/// # Returns
+/// * JSON Schema (draft 2020-12) as a JSON value
+/// * `Err` - If the output can't be narrowed with `--fields`
+pub fn fields_schema_for_target(target: SchemaTarget) -> Result<Value> {
+    if !target.supports_fields() {
+        anyhow::bail!("--fields is not supported for the {} schema", target.name());
+    }
+
+    let mut schema = schema_for_target(target);
+    // Results are referenced from `$defs`, except when a single result is the root
+    let result = match schema.pointer_mut("/$defs/SearchResult") {
+        Some(result) => result,
+        None => &mut schema,
+    };
+    if let Some(result) = result.as_object_mut() {
+        result.remove("required");
+        if let Some(Value::Object(properties)) = result.get_mut("properties") {
+            properties.insert(
+                "alias".to_string(),
+                json!({
+                    "description": "Aliases pointing at the result, comma-separated",
+                    "type": ["string", "null"]
+                }),
+            );
+        }
+    }
+    Ok(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::core::fields::Field;
+    use crate::core::formatting::{
+        DEFAULT_TABLE_WIDTH, OutputFormat, format_envelope, format_search_results,
+    };
+    use crate::core::scoring::{ScoreWeights, rank_by_relevance};
+    use crate::models::{ENVELOPE_SCHEMA_VERSION, EnvelopeFilters};
+
+    fn create_test_results() -> Vec<SearchResult> {
+        vec![
+            SearchResult {
+                id: "/facebook/react".to_string(),
+                title: Some("React".to_string()),
+                description: Some("A JavaScript library for building user interfaces".to_string()),
+                branch: Some("main".to_string()),
+                last_update_date: Some("2025-01-15T10:00:00.000Z".to_string()),
+                state: Some("finalized".to_string()),
+                total_tokens: Some(400000),
+                total_snippets: Some(850),
+                total_pages: Some(150),
+                stars: Some(220000),
+                trust_score: Some(9.5),
+                versions: Some(vec![serde_json::json!("v18.3.1")]),
+            },
+            SearchResult {
+                id: "/minimal/lib".to_string(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn assert_valid(target: SchemaTarget, output: &str) {
+        assert_matches(&schema_for_target(target), target.name(), output);
+    }
+
+    fn assert_valid_fields(target: SchemaTarget, output: &str) {
+        let schema = fields_schema_for_target(target).unwrap();
+        assert_matches(&schema, target.name(), output);
+    }
+
+    fn assert_matches(schema: &Value, name: &str, output: &str) {
+        let instance: Value = serde_json::from_str(output).unwrap();
+        let validator = jsonschema::validator_for(schema).unwrap();
+        let errors: Vec<String> = validator
+            .iter_errors(&instance)
+            .map(|e| e.to_string())
+            .collect();
+        assert!(
+            errors.is_empty(),
+            "{} output does not match its schema: {:?}",
+            name,
+            errors
+        );
+    }
+
+    #[test]
+    fn test_schema_target_from_str() {
+        for target in SchemaTarget::ALL {
+            assert_eq!(SchemaTarget::from_str(target.name()).unwrap(), target);
+        }
+        let error_msg = SchemaTarget::from_str("envelope").unwrap_err().to_string();
+        assert!(error_msg.contains("Valid options are"));
+    }
+
+    #[test]
+    fn test_every_schema_is_a_valid_schema() {
+        for target in SchemaTarget::ALL {
+            let schema = schema_for_target(target);
+            assert!(jsonschema::validator_for(&schema).is_ok());
+            if target.supports_fields() {
+                let schema = fields_schema_for_target(target).unwrap();
+                assert!(jsonschema::validator_for(&schema).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_json_output_matches_schema() {
+        let output = format_search_results(
+            &create_test_results(),
+            OutputFormat::Json,
+            &[],
+            &HashMap::new(),
+            DEFAULT_TABLE_WIDTH,
+        )
+        .unwrap();
+        assert_valid(SchemaTarget::Search, &output);
+    }
+
+    #[test]
+    fn test_search_ndjson_lines_match_schema() {
+        let output = format_search_results(
+            &create_test_results(),
+            OutputFormat::Ndjson,
+            &[],
+            &HashMap::new(),
+            DEFAULT_TABLE_WIDTH,
+        )
+        .unwrap();
+        for line in output.lines() {
+            assert_valid(SchemaTarget::SearchResult, line);
+        }
+    }
+
+    #[test]
+    fn test_json_envelope_output_matches_schema() {
+        let results = create_test_results();
+        let envelope = SearchEnvelope {
+            schema_version: ENVELOPE_SCHEMA_VERSION,
+            query: "react".to_string(),
+            sort_by: "trustScore,-stars".to_string(),
+            weights: None,
+            missing: Some("first".to_string()),
+            ignore_case: Some(true),
+            filters: EnvelopeFilters {
+                min_trust: Some(7.0),
+                updated_since: Some("2025-01-01".to_string()),
+                ..Default::default()
+            },
+            limit: Some(5),
+            page: None,
+            fetched_at: "2025-01-15T10:00:00Z".to_string(),
+            count: results.len(),
+            total: Some(42),
+            results,
+        };
+
+        let output = format_envelope(envelope, &[], &HashMap::new()).unwrap();
+        assert_valid(SchemaTarget::JsonEnvelope, &output);
+    }
+
+    #[test]
+    fn test_json_envelope_rejects_missing_metadata() {
+        let output = format_search_results(
+            &create_test_results(),
+            OutputFormat::Json,
+            &[Field::Id],
+            &HashMap::new(),
+            DEFAULT_TABLE_WIDTH,
+        )
+        .unwrap();
+        let schema = schema_for_target(SchemaTarget::JsonEnvelope);
+        let instance: Value = serde_json::from_str(&output).unwrap();
+        assert!(!jsonschema::is_valid(&schema, &instance));
+    }
+
+    #[test]
+    fn test_fields_output_matches_fields_schemas() {
+        let fields = [Field::Title, Field::Stars, Field::Alias];
+        let aliases = HashMap::from([("/facebook/react".to_string(), "r".to_string())]);
+        let output = format_search_results(
+            &create_test_results(),
+            OutputFormat::Json,
+            &fields,
+            &aliases,
+            DEFAULT_TABLE_WIDTH,
+        )
+        .unwrap();
+        assert_valid_fields(SchemaTarget::Search, &output);
+
+        let output = format_search_results(
+            &create_test_results(),
+            OutputFormat::Ndjson,
+            &fields,
+            &aliases,
+            DEFAULT_TABLE_WIDTH,
+        )
+        .unwrap();
+        for line in output.lines() {
+            assert_valid_fields(SchemaTarget::SearchResult, line);
+        }
+
+        let results = create_test_results();
+        let envelope = SearchEnvelope {
+            schema_version: ENVELOPE_SCHEMA_VERSION,
+            query: "react".to_string(),
+            sort_by: "relevance".to_string(),
+            weights: Some(ScoreWeights::default().describe()),
+            missing: None,
+            ignore_case: None,
+            filters: EnvelopeFilters::default(),
+            limit: None,
+            page: None,
+            fetched_at: "2025-01-15T10:00:00Z".to_string(),
+            count: results.len(),
+            total: None,
+            results,
+        };
+        let output = format_envelope(envelope, &fields, &aliases).unwrap();
+        assert_valid_fields(SchemaTarget::JsonEnvelope, &output);
+    }
+
+    #[test]
+    fn test_fields_output_does_not_match_full_schema() {
+        let output = format_search_results(
+            &create_test_results(),
+            OutputFormat::Json,
+            &[Field::Title, Field::Stars],
+            &HashMap::new(),
+            DEFAULT_TABLE_WIDTH,
+        )
+        .unwrap();
+        let instance: Value = serde_json::from_str(&output).unwrap();
+        assert!(!jsonschema::is_valid(
+            &schema_for_target(SchemaTarget::Search),
+            &instance
+        ));
+    }
+
+    #[test]
+    fn test_fields_schema_keeps_property_types() {
+        let schema = fields_schema_for_target(SchemaTarget::SearchResult).unwrap();
+        let instance = json!({"stars": "many"});
+        assert!(!jsonschema::is_valid(&schema, &instance));
+    }
+
+    #[test]
+    fn test_fields_schema_rejects_other_targets() {
+        assert!(fields_schema_for_target(SchemaTarget::Resolve).is_err());
+        assert!(fields_schema_for_target(SchemaTarget::Docs).is_err());
+    }
+
+    #[test]
+    fn test_docs_output_matches_schema() {
+        let docs = LibraryDocs {
+            id: "/fastapi/fastapi".to_string(),
+            content: "# FastAPI".to_string(),
+        };
+        let output = serde_json::to_string_pretty(&docs).unwrap();
+        assert_valid(SchemaTarget::Docs, &output);
+    }
+
+    #[test]
+    fn test_resolve_output_matches_schema() {
+        let weights = ScoreWeights::default();
+        let ranked = rank_by_relevance("react", create_test_results(), &weights);
+        let searched = Resolution::from_ranked("npm:react", &ranked, &weights).unwrap();
+        let direct = Resolution::direct("https://github.com/facebook/react").unwrap();
+
+        for resolution in [searched, direct] {
+            let output = serde_json::to_string_pretty(&resolution).unwrap();
+            assert_valid(SchemaTarget::Resolve, &output);
+        }
+    }
+}
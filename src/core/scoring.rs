@@ -0,0 +1,389 @@
+use std::cmp::Ordering;
+
+use anyhow::Result;
+
+use crate::models::EnvelopeWeights;
+use crate::models::search::SearchResult;
+
+/// Star count treated as the ceiling of the log-scaled popularity component
+const STARS_CEILING: f64 = 1_000_000.0;
+
+/// Snippet count treated as the ceiling of the log-scaled coverage component
+const SNIPPETS_CEILING: f64 = 10_000.0;
+
+/// Maximum trust score reported by the Context7 API
+const TRUST_CEILING: f64 = 10.0;
+
+/// Relative importance of each component in a relevance score
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreWeights {
+    pub name: f64,
+    pub trust: f64,
+    pub stars: f64,
+    pub snippets: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        // Name match dominates so an exact package beats a popular framework
+        // that merely mentions the query
+        Self {
+            name: 4.0,
+            trust: 1.0,
+            stars: 1.0,
+            snippets: 0.5,
+        }
+    }
+}
+
+impl ScoreWeights {
+    /// Parse weights such as "name=3,stars=0.5"; unspecified components keep their defaults
+    pub fn from_str(s: &str) -> Result<Self> {
+        let mut weights = ScoreWeights::default();
+
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let Some((key, value)) = pair.split_once('=') else {
+                anyhow::bail!(
+                    "Invalid weight '{}'. Expected format: component=number",
+                    pair
+                );
+            };
+            let value: f64 = value
+                .trim()
+                .parse()
+                .ok()
+                .filter(|v: &f64| v.is_finite() && *v >= 0.0)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid weight value '{}' for '{}'. Weights must be non-negative numbers",
+                        value.trim(),
+                        key.trim()
+                    )
+                })?;
+
+            match key.trim() {
+                "name" => weights.name = value,
+                "trust" => weights.trust = value,
+                "stars" => weights.stars = value,
+                "snippets" => weights.snippets = value,
+                other => anyhow::bail!(
+                    "Invalid weight component '{}'. Valid options are: name, trust, stars, snippets",
+                    other
+                ),
+            }
+        }
+
+        Ok(weights)
+    }
+
+    /// Describe the weights for a JSON envelope
+    pub fn describe(&self) -> EnvelopeWeights {
+        EnvelopeWeights {
+            name: self.name,
+            trust: self.trust,
+            stars: self.stars,
+            snippets: self.snippets,
+        }
+    }
+}
+
+/// Each component of a relevance score, normalized to 0.0..=1.0, plus the weighted total
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoreBreakdown {
+    pub name: f64,
+    pub trust: f64,
+    pub stars: f64,
+    pub snippets: f64,
+    pub total: f64,
+}
+
+/// A search result paired with its relevance score
+#[derive(Debug, Clone)]
+pub struct ScoredResult {
+    pub result: SearchResult,
+    pub score: ScoreBreakdown,
+}
+
+/// Score a single result against a query.
+///
+/// # Arguments
+/// * `query` - The query the user searched for
+/// * `result` - Search result to score
+/// * `weights` - Relative importance of each component
+///
+/// # Returns
+/// * Normalized components and their weighted total
+pub fn score_result(query: &str, result: &SearchResult, weights: &ScoreWeights) -> ScoreBreakdown {
+    let name = name_match(query, result);
+    let trust = (result.trust_score.unwrap_or(0.0) / TRUST_CEILING).clamp(0.0, 1.0);
+    let stars = log_scaled(result.stars.unwrap_or(0), STARS_CEILING);
+    let snippets = log_scaled(result.total_snippets.unwrap_or(0), SNIPPETS_CEILING);
+
+    ScoreBreakdown {
+        name,
+        trust,
+        stars,
+        snippets,
+        total: weights.name * name
+            + weights.trust * trust
+            + weights.stars * stars
+            + weights.snippets * snippets,
+    }
+}
+
+/// Score every result and order them from most to least relevant.
+///
+/// # Arguments
+/// * `query` - The query the user searched for
+/// * `results` - Search results to rank
+/// * `weights` - Relative importance of each component
+///
+/// # Returns
+/// * Results with their scores, highest total first (ties broken by `id`)
+pub fn rank_by_relevance(
+    query: &str,
+    results: Vec<SearchResult>,
+    weights: &ScoreWeights,
+) -> Vec<ScoredResult> {
+    let mut scored: Vec<ScoredResult> = results
+        .into_iter()
+        .map(|result| ScoredResult {
+            score: score_result(query, &result, weights),
+            result,
+        })
+        .collect();
+
+    scored.sort_by(|a, b| {
+        b.score
+            .total
+            .partial_cmp(&a.score.total)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.result.id.cmp(&b.result.id))
+    });
+
+    scored
+}
+
+/// How closely the query names this library, from its title and ID.
+///
+/// An exact match on the repository name or title scores 1.0, a prefix match
+/// 0.75, containment 0.5, and otherwise the share of query words found.
+fn name_match(query: &str, result: &SearchResult) -> f64 {
+    let query = normalize(query);
+    if query.is_empty() {
+        return 0.0;
+    }
+
+    let repo = result.id.rsplit('/').find(|s| !s.is_empty()).unwrap_or("");
+    let candidates: Vec<String> = [Some(repo), result.title.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(normalize)
+        .filter(|c| !c.is_empty())
+        .collect();
+
+    let best = candidates
+        .iter()
+        .map(|candidate| {
+            if *candidate == query {
+                1.0
+            } else if candidate.starts_with(&query) || query.starts_with(candidate.as_str()) {
+                0.75
+            } else if candidate.contains(&query) || query.contains(candidate.as_str()) {
+                0.5
+            } else {
+                0.0
+            }
+        })
+        .fold(0.0, f64::max);
+
+    best.max(0.5 * word_overlap(query_words(&query), &result.id, result.title.as_deref()))
+}
+
+/// Lowercase and strip punctuation so "Next.js", "next-js" and "nextjs" compare equal.
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// Split a normalized query into its words.
+fn query_words(query: &str) -> Vec<&str> {
+    query.split_whitespace().collect()
+}
+
+/// Fraction of query words that appear in the ID or title.
+fn word_overlap(words: Vec<&str>, id: &str, title: Option<&str>) -> f64 {
+    if words.is_empty() {
+        return 0.0;
+    }
+    let haystack = format!("{} {}", id, title.unwrap_or("")).to_lowercase();
+    let found = words.iter().filter(|w| haystack.contains(*w)).count();
+    found as f64 / words.len() as f64
+}
+
+/// Map a count onto 0.0..=1.0 logarithmically, reaching 1.0 at `ceiling`.
+fn log_scaled(value: i64, ceiling: f64) -> f64 {
+    let value = value.max(0) as f64;
+    ((value + 1.0).log10() / (ceiling + 1.0).log10()).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_result(
+        id: &str,
+        title: Option<&str>,
+        stars: Option<i64>,
+        trust_score: Option<f64>,
+        total_snippets: Option<i64>,
+    ) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            stars,
+            title: title.map(|s| s.to_string()),
+            total_snippets,
+            trust_score,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_score_weights_from_str_overrides_defaults() {
+        let weights = ScoreWeights::from_str("name=2, stars=0").unwrap();
+        assert_eq!(weights.name, 2.0);
+        assert_eq!(weights.stars, 0.0);
+        assert_eq!(weights.trust, ScoreWeights::default().trust);
+    }
+
+    #[test]
+    fn test_score_weights_from_str_invalid() {
+        assert!(ScoreWeights::from_str("popularity=1").is_err());
+        assert!(ScoreWeights::from_str("name").is_err());
+        assert!(ScoreWeights::from_str("name=abc").is_err());
+        assert!(ScoreWeights::from_str("name=-1").is_err());
+    }
+
+    #[test]
+    fn test_name_match_exact_repo_ignores_punctuation_and_case() {
+        let result = create_test_result("/vercel/next.js", Some("Next.js"), None, None, None);
+        assert_eq!(name_match("nextjs", &result), 1.0);
+        assert_eq!(name_match("Next.js", &result), 1.0);
+    }
+
+    #[test]
+    fn test_name_match_prefix_and_containment() {
+        let result = create_test_result("/tiangolo/fastapi-users", None, None, None, None);
+        assert_eq!(name_match("fastapi", &result), 0.75);
+
+        let result = create_test_result("/org/awesome-fastapi", None, None, None, None);
+        assert_eq!(name_match("fastapi", &result), 0.5);
+    }
+
+    #[test]
+    fn test_name_match_unrelated() {
+        let result = create_test_result("/facebook/react", Some("React"), None, None, None);
+        assert_eq!(name_match("django", &result), 0.0);
+    }
+
+    #[test]
+    fn test_log_scaled_bounds() {
+        assert_eq!(log_scaled(0, STARS_CEILING), 0.0);
+        assert_eq!(log_scaled(-5, STARS_CEILING), 0.0);
+        assert_eq!(log_scaled(5_000_000, STARS_CEILING), 1.0);
+        let mid = log_scaled(1000, STARS_CEILING);
+        assert!(mid > 0.49 && mid < 0.51);
+    }
+
+    #[test]
+    fn test_score_result_components_are_normalized() {
+        let result = create_test_result(
+            "/pallets/flask",
+            Some("Flask"),
+            Some(65000),
+            Some(9.0),
+            Some(800),
+        );
+
+        let score = score_result("flask", &result, &ScoreWeights::default());
+
+        for component in [score.name, score.trust, score.stars, score.snippets] {
+            assert!((0.0..=1.0).contains(&component));
+        }
+        assert_eq!(score.name, 1.0);
+        assert_eq!(score.trust, 0.9);
+    }
+
+    #[test]
+    fn test_score_result_total_is_weighted_sum() {
+        let result = create_test_result("/a/b", Some("B"), Some(100), Some(5.0), Some(10));
+        let weights = ScoreWeights {
+            name: 0.0,
+            trust: 2.0,
+            stars: 0.0,
+            snippets: 0.0,
+        };
+
+        let score = score_result("b", &result, &weights);
+
+        assert_eq!(score.total, 1.0);
+    }
+
+    #[test]
+    fn test_rank_prefers_exact_package_over_popular_framework() {
+        let results = vec![
+            create_test_result(
+                "/facebook/react",
+                Some("React"),
+                Some(220000),
+                Some(9.5),
+                Some(850),
+            ),
+            create_test_result(
+                "/pmndrs/zustand",
+                Some("Zustand"),
+                Some(45000),
+                Some(8.0),
+                Some(120),
+            ),
+        ];
+
+        let ranked = rank_by_relevance("zustand", results, &ScoreWeights::default());
+
+        assert_eq!(ranked[0].result.id, "/pmndrs/zustand");
+    }
+
+    #[test]
+    fn test_rank_falls_back_to_metrics_without_name_weight() {
+        let results = vec![
+            create_test_result("/small/exact", Some("exact"), Some(10), Some(3.0), None),
+            create_test_result("/big/other", Some("other"), Some(90000), Some(9.5), None),
+        ];
+        let weights = ScoreWeights {
+            name: 0.0,
+            ..Default::default()
+        };
+
+        let ranked = rank_by_relevance("exact", results, &weights);
+
+        assert_eq!(ranked[0].result.id, "/big/other");
+    }
+
+    #[test]
+    fn test_rank_ties_broken_by_id() {
+        let results = vec![
+            create_test_result("/b/lib", None, None, None, None),
+            create_test_result("/a/lib", None, None, None, None),
+        ];
+
+        let ranked = rank_by_relevance("nothing", results, &ScoreWeights::default());
+
+        assert_eq!(ranked[0].result.id, "/a/lib");
+        assert_eq!(ranked[1].result.id, "/b/lib");
+    }
+}
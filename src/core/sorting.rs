@@ -1,5 +1,9 @@
+use std::cmp::Ordering;
+
 use anyhow::Result;
+use chrono::NaiveDate;
 
+use crate::core::dates::parse_date;
 use crate::models::search::SearchResult;
 
 /// Fields that can be used to sort search results
@@ -10,9 +14,23 @@ pub enum SortField {
     TotalSnippets,
     TotalTokens,
     TrustScore,
+    Title,
+    Id,
+    LastUpdateDate,
 }
 
 impl SortField {
+    pub const ALL: [SortField; 8] = [
+        SortField::Stars,
+        SortField::TotalPages,
+        SortField::TotalSnippets,
+        SortField::TotalTokens,
+        SortField::TrustScore,
+        SortField::Title,
+        SortField::Id,
+        SortField::LastUpdateDate,
+    ];
+
     /// Parse a string into a SortField
     pub fn from_str(s: &str) -> Result<Self> {
         match s {
@@ -21,47 +39,260 @@ impl SortField {
             "totalSnippets" => Ok(SortField::TotalSnippets),
             "totalTokens" => Ok(SortField::TotalTokens),
             "trustScore" => Ok(SortField::TrustScore),
+            "title" => Ok(SortField::Title),
+            "id" => Ok(SortField::Id),
+            "lastUpdateDate" => Ok(SortField::LastUpdateDate),
             _ => anyhow::bail!(
-                "Invalid sort field '{}'. Valid options are: stars, totalPages, totalSnippets, totalTokens, trustScore",
+                "Invalid sort field '{}'. Valid options are: stars, totalPages, totalSnippets, totalTokens, trustScore, title, id, lastUpdateDate",
                 s
             ),
         }
     }
+
+    /// Name of the field as accepted by `--sort-by`
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortField::Stars => "stars",
+            SortField::TotalPages => "totalPages",
+            SortField::TotalSnippets => "totalSnippets",
+            SortField::TotalTokens => "totalTokens",
+            SortField::TrustScore => "trustScore",
+            SortField::Title => "title",
+            SortField::Id => "id",
+            SortField::LastUpdateDate => "lastUpdateDate",
+        }
+    }
+
+    /// Direction used when a sort key has no `+`/`-` prefix: metrics and
+    /// dates put the largest or most recent first, text sorts alphabetically.
+    pub fn default_direction(&self) -> SortDirection {
+        match self {
+            SortField::Title | SortField::Id => SortDirection::Ascending,
+            _ => SortDirection::Descending,
+        }
+    }
+}
+
+/// A comparable value extracted from a result for one sort field
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+enum SortValue {
+    Number(f64),
+    Text(String),
+    Date(NaiveDate),
+}
+
+/// Direction in which a sort key orders results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// Where results missing a sort key's value are placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingPlacement {
+    First,
+    Last,
+}
+
+impl MissingPlacement {
+    /// Parse a string into a MissingPlacement
+    pub fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "first" => Ok(MissingPlacement::First),
+            "last" => Ok(MissingPlacement::Last),
+            _ => anyhow::bail!(
+                "Invalid missing value placement '{}'. Valid options are: first, last",
+                s
+            ),
+        }
+    }
+
+    /// Name of the placement as accepted by `--missing`
+    pub fn name(&self) -> &'static str {
+        match self {
+            MissingPlacement::First => "first",
+            MissingPlacement::Last => "last",
+        }
+    }
+}
+
+/// A single field to sort by, with its direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: SortField,
+    pub direction: SortDirection,
+}
+
+impl SortKey {
+    /// Parse a sort key, where a `+` prefix sorts ascending and a `-` prefix
+    /// sorts descending. Without a prefix, the field's default direction is used.
+    pub fn from_str(s: &str) -> Result<Self> {
+        let (direction, name) = if let Some(name) = s.strip_prefix('+') {
+            (Some(SortDirection::Ascending), name)
+        } else if let Some(name) = s.strip_prefix('-') {
+            (Some(SortDirection::Descending), name)
+        } else {
+            (None, s)
+        };
+
+        let field = SortField::from_str(name)?;
+        Ok(SortKey {
+            field,
+            direction: direction.unwrap_or_else(|| field.default_direction()),
+        })
+    }
+}
+
+/// A complete ordering: keys applied in priority order, then a tie-break on `id`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortSpec {
+    pub keys: Vec<SortKey>,
+    pub missing: MissingPlacement,
+    pub ignore_case: bool,
 }
 
-/// Sort search results by a specified field in descending order.
+impl SortSpec {
+    /// Parse a comma-separated list of sort keys (e.g. "trustScore,-stars,+totalTokens").
+    ///
+    /// Missing values are placed last; use `with_missing` to change that.
+    pub fn from_str(s: &str) -> Result<Self> {
+        let keys = s
+            .split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(SortKey::from_str)
+            .collect::<Result<Vec<SortKey>>>()?;
+
+        if keys.is_empty() {
+            anyhow::bail!("At least one sort field must be specified");
+        }
+
+        Ok(SortSpec {
+            keys,
+            missing: MissingPlacement::Last,
+            ignore_case: false,
+        })
+    }
+
+    /// Sort by a single field in its default direction, placing missing values last
+    pub fn single(field: SortField) -> Self {
+        SortSpec {
+            keys: vec![SortKey {
+                field,
+                direction: field.default_direction(),
+            }],
+            missing: MissingPlacement::Last,
+            ignore_case: false,
+        }
+    }
+
+    /// Change where results missing a sort value are placed
+    pub fn with_missing(mut self, missing: MissingPlacement) -> Self {
+        self.missing = missing;
+        self
+    }
+
+    /// Compare `title` and `id` without regard to letter case
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+}
+
+/// Sort search results by a specified field in its default direction.
 ///
 /// # Arguments
 /// * `results` - Vector of search results to sort
 /// * `sort_by` - Field to sort by
 ///
 /// # Returns
-/// * Sorted results (highest values first)
-pub fn sort_search_results(
+/// * Sorted results (highest values first for metrics, missing values last)
+pub fn sort_search_results(results: Vec<SearchResult>, sort_by: SortField) -> Vec<SearchResult> {
+    sort_search_results_by(results, &SortSpec::single(sort_by))
+}
+
+/// Sort search results by every key in a sort spec.
+///
+/// Keys are compared in order; later keys only break ties left by earlier
+/// ones. Results that still compare equal are ordered by `id` so output is
+/// deterministic regardless of the order the API returned them in.
+///
+/// # Arguments
+/// * `results` - Vector of search results to sort
+/// * `spec` - Keys, directions and missing value placement
+///
+/// # Returns
+/// * Sorted results
+pub fn sort_search_results_by(
     mut results: Vec<SearchResult>,
-    sort_by: SortField,
+    spec: &SortSpec,
 ) -> Vec<SearchResult> {
-    results.sort_by(|a, b| match sort_by {
-        SortField::Stars => b.stars.unwrap_or(0).cmp(&a.stars.unwrap_or(0)),
-        SortField::TotalPages => b.total_pages.unwrap_or(0).cmp(&a.total_pages.unwrap_or(0)),
-        SortField::TotalSnippets => b
-            .total_snippets
-            .unwrap_or(0)
-            .cmp(&a.total_snippets.unwrap_or(0)),
-        SortField::TotalTokens => b
-            .total_tokens
-            .unwrap_or(0)
-            .cmp(&a.total_tokens.unwrap_or(0)),
-        SortField::TrustScore => b
-            .trust_score
-            .unwrap_or(0.0)
-            .partial_cmp(&a.trust_score.unwrap_or(0.0))
-            .unwrap_or(std::cmp::Ordering::Equal),
+    results.sort_by(|a, b| {
+        spec.keys
+            .iter()
+            .map(|key| compare_by_key(a, b, *key, spec))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or_else(|| a.id.cmp(&b.id))
     });
 
     results
 }
 
+/// Compare two results on one sort key, honouring direction and missing value placement.
+fn compare_by_key(a: &SearchResult, b: &SearchResult, key: SortKey, spec: &SortSpec) -> Ordering {
+    let missing = spec.missing;
+    match (
+        sort_value(a, key.field, spec.ignore_case),
+        sort_value(b, key.field, spec.ignore_case),
+    ) {
+        (Some(a_value), Some(b_value)) => {
+            let ordering = a_value.partial_cmp(&b_value).unwrap_or(Ordering::Equal);
+            match key.direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        }
+        (Some(_), None) => match missing {
+            MissingPlacement::First => Ordering::Greater,
+            MissingPlacement::Last => Ordering::Less,
+        },
+        (None, Some(_)) => match missing {
+            MissingPlacement::First => Ordering::Less,
+            MissingPlacement::Last => Ordering::Greater,
+        },
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Extract the value of a sort field from a result.
+///
+/// Dates that fail to parse are treated as missing rather than compared as text.
+fn sort_value(result: &SearchResult, field: SortField, ignore_case: bool) -> Option<SortValue> {
+    let text = |s: &str| {
+        SortValue::Text(if ignore_case {
+            s.to_lowercase()
+        } else {
+            s.to_string()
+        })
+    };
+
+    match field {
+        SortField::Stars => result.stars.map(|v| SortValue::Number(v as f64)),
+        SortField::TotalPages => result.total_pages.map(|v| SortValue::Number(v as f64)),
+        SortField::TotalSnippets => result.total_snippets.map(|v| SortValue::Number(v as f64)),
+        SortField::TotalTokens => result.total_tokens.map(|v| SortValue::Number(v as f64)),
+        SortField::TrustScore => result.trust_score.map(SortValue::Number),
+        SortField::Title => result.title.as_deref().map(text),
+        SortField::Id => Some(text(&result.id)),
+        SortField::LastUpdateDate => result
+            .last_update_date
+            .as_deref()
+            .and_then(parse_date)
+            .map(SortValue::Date),
+    }
+}
+
 /// Apply a limit to the number of results.
 ///
 /// # Arguments
@@ -89,17 +320,12 @@ mod tests {
     ) -> SearchResult {
         SearchResult {
             id: id.to_string(),
-            branch: None,
-            description: None,
-            last_update_date: None,
             stars,
-            state: None,
-            title: None,
             total_pages,
             total_snippets,
             total_tokens,
             trust_score,
-            versions: None,
+            ..Default::default()
         }
     }
 
@@ -122,6 +348,12 @@ mod tests {
             SortField::from_str("trustScore").unwrap(),
             SortField::TrustScore
         );
+        assert_eq!(SortField::from_str("title").unwrap(), SortField::Title);
+        assert_eq!(SortField::from_str("id").unwrap(), SortField::Id);
+        assert_eq!(
+            SortField::from_str("lastUpdateDate").unwrap(),
+            SortField::LastUpdateDate
+        );
     }
 
     #[test]
@@ -136,6 +368,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sort_field_name_round_trips() {
+        for field in SortField::ALL {
+            assert_eq!(SortField::from_str(field.name()).unwrap(), field);
+        }
+    }
+
     #[test]
     fn test_sort_by_stars_descending() {
         let results = vec![
@@ -300,4 +539,286 @@ mod tests {
 
         assert_eq!(limited.len(), 0);
     }
+
+    fn ids(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_key_from_str_directions() {
+        assert_eq!(
+            SortKey::from_str("stars").unwrap(),
+            SortKey {
+                field: SortField::Stars,
+                direction: SortDirection::Descending
+            }
+        );
+        assert_eq!(
+            SortKey::from_str("-stars").unwrap().direction,
+            SortDirection::Descending
+        );
+        assert_eq!(
+            SortKey::from_str("+stars").unwrap().direction,
+            SortDirection::Ascending
+        );
+        assert!(SortKey::from_str("-bogus").is_err());
+    }
+
+    #[test]
+    fn test_sort_spec_from_str_multiple_keys() {
+        let spec = SortSpec::from_str("trustScore, -stars,+totalTokens").unwrap();
+
+        assert_eq!(spec.keys.len(), 3);
+        assert_eq!(spec.keys[0].field, SortField::TrustScore);
+        assert_eq!(spec.keys[1].field, SortField::Stars);
+        assert_eq!(spec.keys[2].field, SortField::TotalTokens);
+        assert_eq!(spec.keys[2].direction, SortDirection::Ascending);
+        assert_eq!(spec.missing, MissingPlacement::Last);
+    }
+
+    #[test]
+    fn test_sort_spec_from_str_empty() {
+        assert!(SortSpec::from_str("").is_err());
+        assert!(SortSpec::from_str(" , ").is_err());
+    }
+
+    #[test]
+    fn test_missing_placement_from_str() {
+        assert_eq!(
+            MissingPlacement::from_str("first").unwrap(),
+            MissingPlacement::First
+        );
+        assert_eq!(
+            MissingPlacement::from_str("last").unwrap(),
+            MissingPlacement::Last
+        );
+        assert!(MissingPlacement::from_str("middle").is_err());
+    }
+
+    #[test]
+    fn test_sort_ascending() {
+        let results = vec![
+            create_test_result("medium", Some(50), None, None, None, None),
+            create_test_result("high", Some(100), None, None, None, None),
+            create_test_result("low", Some(10), None, None, None, None),
+        ];
+
+        let sorted = sort_search_results_by(results, &SortSpec::from_str("+stars").unwrap());
+
+        assert_eq!(ids(&sorted), vec!["low", "medium", "high"]);
+    }
+
+    #[test]
+    fn test_sort_multi_key_breaks_ties_with_later_keys() {
+        let results = vec![
+            create_test_result("trusted_small", Some(10), None, None, None, Some(9.0)),
+            create_test_result("untrusted", Some(500), None, None, None, Some(5.0)),
+            create_test_result("trusted_big", Some(900), None, None, None, Some(9.0)),
+        ];
+
+        let sorted =
+            sort_search_results_by(results, &SortSpec::from_str("trustScore,-stars").unwrap());
+
+        assert_eq!(
+            ids(&sorted),
+            vec!["trusted_big", "trusted_small", "untrusted"]
+        );
+    }
+
+    #[test]
+    fn test_sort_multi_key_mixed_directions() {
+        let results = vec![
+            create_test_result("a", Some(10), Some(3), None, None, None),
+            create_test_result("b", Some(10), Some(1), None, None, None),
+            create_test_result("c", Some(20), Some(2), None, None, None),
+        ];
+
+        let sorted =
+            sort_search_results_by(results, &SortSpec::from_str("stars,+totalPages").unwrap());
+
+        assert_eq!(ids(&sorted), vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_sort_missing_values_last_in_both_directions() {
+        let results = vec![
+            create_test_result("none", None, None, None, None, None),
+            create_test_result("low", Some(1), None, None, None, None),
+            create_test_result("high", Some(9), None, None, None, None),
+        ];
+
+        let descending =
+            sort_search_results_by(results.clone(), &SortSpec::from_str("stars").unwrap());
+        let ascending = sort_search_results_by(results, &SortSpec::from_str("+stars").unwrap());
+
+        assert_eq!(ids(&descending), vec!["high", "low", "none"]);
+        assert_eq!(ids(&ascending), vec!["low", "high", "none"]);
+    }
+
+    #[test]
+    fn test_sort_missing_values_first() {
+        let results = vec![
+            create_test_result("high", Some(9), None, None, None, None),
+            create_test_result("none", None, None, None, None, None),
+        ];
+
+        let spec = SortSpec::from_str("stars")
+            .unwrap()
+            .with_missing(MissingPlacement::First);
+        let sorted = sort_search_results_by(results, &spec);
+
+        assert_eq!(ids(&sorted), vec!["none", "high"]);
+    }
+
+    #[test]
+    fn test_sort_ties_broken_by_id() {
+        let results = vec![
+            create_test_result("/c/lib", Some(5), None, None, None, None),
+            create_test_result("/a/lib", Some(5), None, None, None, None),
+            create_test_result("/b/lib", None, None, None, None, None),
+            create_test_result("/d/lib", None, None, None, None, None),
+        ];
+
+        let sorted = sort_search_results(results, SortField::Stars);
+
+        assert_eq!(ids(&sorted), vec!["/a/lib", "/c/lib", "/b/lib", "/d/lib"]);
+    }
+
+    fn create_dated_result(id: &str, title: Option<&str>, date: Option<&str>) -> SearchResult {
+        SearchResult {
+            title: title.map(|s| s.to_string()),
+            last_update_date: date.map(|s| s.to_string()),
+            ..create_test_result(id, None, None, None, None, None)
+        }
+    }
+
+    #[test]
+    fn test_sort_field_default_directions() {
+        assert_eq!(
+            SortField::Stars.default_direction(),
+            SortDirection::Descending
+        );
+        assert_eq!(
+            SortField::LastUpdateDate.default_direction(),
+            SortDirection::Descending
+        );
+        assert_eq!(
+            SortField::Title.default_direction(),
+            SortDirection::Ascending
+        );
+        assert_eq!(SortField::Id.default_direction(), SortDirection::Ascending);
+    }
+
+    #[test]
+    fn test_sort_by_title_alphabetical() {
+        let results = vec![
+            create_dated_result("1", Some("Vue"), None),
+            create_dated_result("2", Some("Angular"), None),
+            create_dated_result("3", Some("React"), None),
+        ];
+
+        let sorted = sort_search_results(results, SortField::Title);
+
+        assert_eq!(ids(&sorted), vec!["2", "3", "1"]);
+    }
+
+    #[test]
+    fn test_sort_by_title_descending_with_prefix() {
+        let results = vec![
+            create_dated_result("1", Some("Angular"), None),
+            create_dated_result("2", Some("Vue"), None),
+        ];
+
+        let sorted = sort_search_results_by(results, &SortSpec::from_str("-title").unwrap());
+
+        assert_eq!(ids(&sorted), vec!["2", "1"]);
+    }
+
+    #[test]
+    fn test_sort_by_id_case_sensitive_by_default() {
+        let results = vec![
+            create_dated_result("/b/lib", None, None),
+            create_dated_result("/a/lib", None, None),
+            create_dated_result("/C/lib", None, None),
+        ];
+
+        let sorted = sort_search_results(results, SortField::Id);
+
+        // Uppercase letters sort before lowercase in byte order
+        assert_eq!(ids(&sorted), vec!["/C/lib", "/a/lib", "/b/lib"]);
+    }
+
+    #[test]
+    fn test_sort_by_id_ignore_case() {
+        let results = vec![
+            create_dated_result("/b/lib", None, None),
+            create_dated_result("/a/lib", None, None),
+            create_dated_result("/C/lib", None, None),
+        ];
+
+        let spec = SortSpec::from_str("id").unwrap().with_ignore_case(true);
+        let sorted = sort_search_results_by(results, &spec);
+
+        assert_eq!(ids(&sorted), vec!["/a/lib", "/b/lib", "/C/lib"]);
+    }
+
+    #[test]
+    fn test_sort_by_title_ignore_case() {
+        let results = vec![
+            create_dated_result("1", Some("vue"), None),
+            create_dated_result("2", Some("Angular"), None),
+            create_dated_result("3", Some("react"), None),
+        ];
+
+        let spec = SortSpec::from_str("title").unwrap().with_ignore_case(true);
+        let sorted = sort_search_results_by(results, &spec);
+
+        assert_eq!(ids(&sorted), vec!["2", "3", "1"]);
+    }
+
+    #[test]
+    fn test_sort_by_last_update_date_most_recent_first() {
+        let results = vec![
+            create_dated_result("old", None, Some("2024-12-31")),
+            create_dated_result("new", None, Some("2025-02-01T08:00:00.000Z")),
+            create_dated_result("mid", None, Some("2025-01-15")),
+        ];
+
+        let sorted = sort_search_results(results, SortField::LastUpdateDate);
+
+        assert_eq!(ids(&sorted), vec!["new", "mid", "old"]);
+    }
+
+    #[test]
+    fn test_sort_by_last_update_date_compares_dates_not_strings() {
+        // As strings the timestamp would sort first; as dates both are the same
+        // day, so the tie is broken by id
+        let results = vec![
+            create_dated_result("/b/timestamp", None, Some("2025-01-20T23:59:59.000Z")),
+            create_dated_result("/a/plain", None, Some("2025-01-20")),
+        ];
+
+        let sorted = sort_search_results(results, SortField::LastUpdateDate);
+
+        assert_eq!(ids(&sorted), vec!["/a/plain", "/b/timestamp"]);
+    }
+
+    #[test]
+    fn test_sort_by_last_update_date_malformed_dates_are_missing() {
+        let results = vec![
+            create_dated_result("garbage", None, Some("not-a-date")),
+            create_dated_result("valid", None, Some("2025-01-01")),
+            create_dated_result("impossible", None, Some("2025-02-30")),
+            create_dated_result("absent", None, None),
+        ];
+
+        let sorted = sort_search_results(results.clone(), SortField::LastUpdateDate);
+        assert_eq!(sorted[0].id, "valid");
+
+        let spec = SortSpec::from_str("lastUpdateDate")
+            .unwrap()
+            .with_missing(MissingPlacement::First);
+        let sorted = sort_search_results_by(results, &spec);
+        assert_eq!(sorted[3].id, "valid");
+    }
 }
@@ -1,7 +1,45 @@
+use std::fmt;
+
 use anyhow::Result;
 
+use crate::core::scoring::ScoredResult;
 use crate::models::search::SearchResult;
 
+/// Top candidates whose scores are within this fraction of the best are ambiguous
+pub const AMBIGUITY_MARGIN: f64 = 0.05;
+
+/// Raised when lucky cannot confidently choose between its top candidates
+#[derive(Debug)]
+pub struct AmbiguousMatchError {
+    pub query: String,
+    pub candidates: Vec<(String, f64)>,
+}
+
+impl AmbiguousMatchError {
+    /// Process exit code used when a match is ambiguous
+    pub const EXIT_CODE: u8 = 3;
+}
+
+impl fmt::Display for AmbiguousMatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Ambiguous match for query '{}'; the top candidates scored within {:.0}% of each other:",
+            self.query,
+            AMBIGUITY_MARGIN * 100.0
+        )?;
+        for (id, score) in &self.candidates {
+            writeln!(f, "  {} (score {:.2})", id, score)?;
+        }
+        write!(
+            f,
+            "Use --force to take the top result, --pick to choose, or get-docs with one of the IDs above"
+        )
+    }
+}
+
+impl std::error::Error for AmbiguousMatchError {}
+
 /// Validate that search results are not empty.
 ///
 /// # Arguments
@@ -18,25 +56,64 @@ pub fn validate_search_results_not_empty(results: &[SearchResult], query: &str)
     Ok(())
 }
 
+/// Check that the best ranked candidate clearly beats the runner-up.
+///
+/// # Arguments
+/// * `ranked` - Candidates ordered from most to least relevant
+/// * `query` - The query string (used in error message)
+///
+/// # Returns
+/// * `Ok(())` - If there is at most one candidate or the winner leads by more than `AMBIGUITY_MARGIN`
+/// * `Err(AmbiguousMatchError)` - Listing every candidate within the margin of the winner
+pub fn validate_unambiguous(ranked: &[ScoredResult], query: &str) -> Result<()> {
+    let Some(best) = ranked.first().map(|scored| scored.score.total) else {
+        return Ok(());
+    };
+    let threshold = best - best.abs() * AMBIGUITY_MARGIN;
+
+    let candidates: Vec<(String, f64)> = ranked
+        .iter()
+        .take_while(|scored| scored.score.total >= threshold)
+        .map(|scored| (scored.result.id.clone(), scored.score.total))
+        .collect();
+
+    if candidates.len() > 1 {
+        return Err(AmbiguousMatchError {
+            query: query.to_string(),
+            candidates,
+        }
+        .into());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::scoring::ScoreBreakdown;
+
+    fn scored(id: &str, total: f64) -> ScoredResult {
+        ScoredResult {
+            result: SearchResult {
+                id: id.to_string(),
+                ..Default::default()
+            },
+            score: ScoreBreakdown {
+                name: 0.0,
+                trust: 0.0,
+                stars: 0.0,
+                snippets: 0.0,
+                total,
+            },
+        }
+    }
 
     #[test]
     fn test_validate_search_results_not_empty_with_results() {
         let results = vec![SearchResult {
             id: "/test/lib".to_string(),
-            branch: None,
-            description: None,
-            last_update_date: None,
             stars: Some(100),
-            state: None,
-            title: None,
-            total_pages: None,
-            total_snippets: None,
-            total_tokens: None,
-            trust_score: None,
-            versions: None,
+            ..Default::default()
         }];
 
         assert!(validate_search_results_not_empty(&results, "test query").is_ok());
@@ -70,34 +147,67 @@ mod tests {
         let results = vec![
             SearchResult {
                 id: "/test/lib1".to_string(),
-                branch: None,
-                description: None,
-                last_update_date: None,
                 stars: Some(100),
-                state: None,
-                title: None,
-                total_pages: None,
-                total_snippets: None,
-                total_tokens: None,
-                trust_score: None,
-                versions: None,
+                ..Default::default()
             },
             SearchResult {
                 id: "/test/lib2".to_string(),
-                branch: None,
-                description: None,
-                last_update_date: None,
                 stars: Some(50),
-                state: None,
-                title: None,
-                total_pages: None,
-                total_snippets: None,
-                total_tokens: None,
-                trust_score: None,
-                versions: None,
+                ..Default::default()
             },
         ];
 
         assert!(validate_search_results_not_empty(&results, "test").is_ok());
     }
+
+    #[test]
+    fn test_validate_unambiguous_clear_winner() {
+        let ranked = vec![scored("/a/lib", 6.0), scored("/b/lib", 2.0)];
+        assert!(validate_unambiguous(&ranked, "lib").is_ok());
+    }
+
+    #[test]
+    fn test_validate_unambiguous_single_or_no_candidates() {
+        assert!(validate_unambiguous(&[scored("/a/lib", 1.0)], "lib").is_ok());
+        assert!(validate_unambiguous(&[], "lib").is_ok());
+    }
+
+    #[test]
+    fn test_validate_unambiguous_close_scores() {
+        let ranked = vec![
+            scored("/a/lib", 2.21),
+            scored("/b/lib", 2.15),
+            scored("/c/lib", 2.12),
+            scored("/d/lib", 1.0),
+        ];
+
+        let error = validate_unambiguous(&ranked, "lib").unwrap_err();
+        let ambiguous = error.downcast_ref::<AmbiguousMatchError>().unwrap();
+
+        assert_eq!(ambiguous.query, "lib");
+        let ids: Vec<&str> = ambiguous
+            .candidates
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["/a/lib", "/b/lib", "/c/lib"]);
+    }
+
+    #[test]
+    fn test_ambiguous_match_error_message_lists_candidates() {
+        let error = AmbiguousMatchError {
+            query: "router".to_string(),
+            candidates: vec![
+                ("/a/router".to_string(), 2.0),
+                ("/b/router".to_string(), 1.98),
+            ],
+        };
+
+        let message = error.to_string();
+
+        assert!(message.contains("Ambiguous match for query 'router'"));
+        assert!(message.contains("/a/router (score 2.00)"));
+        assert!(message.contains("/b/router (score 1.98)"));
+        assert!(message.contains("--force"));
+    }
 }
@@ -1,37 +1,306 @@
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+
 use anyhow::Result;
-use clap::Parser;
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
+use clap_complete::CompleteEnv;
 
 mod cli;
 mod commands;
 mod core;
+mod ui;
 
 // Use the modules from the library
 use context7_cli::clients;
 use context7_cli::models;
 
-use cli::{Cli, Commands};
-use clients::Context7Client;
+use cli::{AliasAction, Cli, Commands, ConfigAction, HistoryAction, SearchArgs};
+use clients::{CachedClient, ClientConfig, Context7Client, HistoryClient};
+use core::alias::expand_alias;
+use core::color::ColorChoice;
+use core::completion::COMPLETE_VAR;
+use core::config::{
+    ConfigFile, SettingSource, Settings, default_config_path, default_history_path,
+    default_shell_history_path,
+};
+use core::markdown::DocsFormat;
+use core::resolve::ResolveFormat;
+use core::validation::AmbiguousMatchError;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
-    let client = Context7Client::new();
+async fn main() -> ExitCode {
+    // When invoked by a completion script, print candidates and exit
+    CompleteEnv::with_factory(Cli::command)
+        .var(COMPLETE_VAR)
+        .complete();
+
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {:?}", err);
+            exit_code_for(&err)
+        }
+    }
+}
+
+/// Map an error onto the process exit code scripts can branch on.
+fn exit_code_for(err: &anyhow::Error) -> ExitCode {
+    if err.downcast_ref::<AmbiguousMatchError>().is_some() {
+        ExitCode::from(AmbiguousMatchError::EXIT_CODE)
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// The client and settings for commands that talk to the API, resolved from
+/// flags, the environment and the config file.
+///
+/// Only those commands load it, so a broken config file doesn't stop
+/// `config path`, `alias` or `schema` from running.
+struct Session {
+    config: ConfigFile,
+    settings: Settings,
+    client: HistoryClient<CachedClient<Context7Client>>,
+}
+
+impl Session {
+    fn open(config_path: Option<&Path>, matches: &ArgMatches) -> Result<Self> {
+        let config = ConfigFile::load(config_path)?;
+        let settings = Settings::resolve(cli::overrides(matches)?, &config)?;
+
+        let network_client = Context7Client::with_config(ClientConfig {
+            base_url: settings.base_url.value.clone(),
+            timeout: (settings.timeout.value > 0)
+                .then(|| Duration::from_secs(settings.timeout.value)),
+            api_key: settings.api_key.value.clone(),
+        })?;
+        let cached_client = CachedClient::new(
+            network_client,
+            settings.cache_dir.value.clone(),
+            settings.offline.value,
+        );
+        let client = HistoryClient::new(
+            cached_client,
+            default_history_path(),
+            matches.subcommand_name().unwrap_or_default(),
+        );
+
+        Ok(Session {
+            config,
+            settings,
+            client,
+        })
+    }
+}
+
+async fn run() -> Result<()> {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+
+    let config_path = cli.config.clone().or_else(default_config_path);
+    let history_path = default_history_path();
 
     match cli.command {
-        Commands::Search {
+        Commands::Search(args) => {
+            let SearchArgs {
+                query,
+                // Resolved with the config file and environment into `settings`
+                sort_by: _,
+                missing,
+                ignore_case,
+                weights,
+                limit: _,
+                id_only,
+                format: _,
+                fields,
+                template,
+                template_file,
+                min_stars,
+                min_trust,
+                min_snippets,
+                state,
+                updated_since,
+                interactive,
+                page,
+                all,
+            } = *args;
+            let Session {
+                config,
+                settings,
+                client,
+            } = Session::open(cli.config.as_deref(), &matches)?;
+            // clap rejects --format next to these options, but a format from the
+            // config file is only a default and yields to them
+            let chooses_output =
+                id_only || template.is_some() || template_file.is_some() || interactive;
+            let format = settings.format.value.filter(|_| !chooses_output);
+            // Docs are only printed for the result picked with --interactive
+            let docs_format = if interactive {
+                let color = ColorChoice::from_str(&settings.color.value)?;
+                DocsFormat::for_stdout(false, color)
+            } else {
+                DocsFormat::Raw
+            };
+            let options = commands::search::SearchOptions {
+                sort_by: settings.sort_by.value,
+                missing,
+                ignore_case,
+                weights,
+                limit: settings.limit.value,
+                id_only,
+                format,
+                fields,
+                template,
+                template_file,
+                min_stars,
+                min_trust,
+                min_snippets,
+                state,
+                updated_since,
+                interactive,
+                page,
+                all,
+                aliases: config.aliases,
+                docs_format,
+            };
+            commands::search::execute(&client, query, options).await?;
+        }
+        Commands::GetDocs {
+            id,
+            recent: _,
+            raw,
+            json,
+        } => {
+            let Session {
+                config,
+                settings,
+                client,
+            } = Session::open(cli.config.as_deref(), &matches)?;
+            // clap requires either an ID or --recent
+            let id = match id {
+                Some(id) => expand_alias(&id, &config.aliases),
+                None => commands::get_docs::pick_recent(history_path.as_deref())?,
+            };
+            let output = if json {
+                commands::get_docs::DocsOutput::Json
+            } else {
+                let color = ColorChoice::from_str(&settings.color.value)?;
+                commands::get_docs::DocsOutput::Text(DocsFormat::for_stdout(raw, color))
+            };
+            commands::get_docs::execute(&client, id, output).await?;
+        }
+        Commands::Lucky {
             query,
-            sort_by,
-            limit,
-            id_only,
+            weights,
+            explain,
+            pick,
+            force,
+            raw,
         } => {
-            commands::search::execute(&client, query, sort_by, limit, id_only).await?;
+            let Session {
+                config,
+                settings,
+                client,
+            } = Session::open(cli.config.as_deref(), &matches)?;
+            let color = ColorChoice::from_str(&settings.color.value)?;
+            let options = commands::lucky::LuckyOptions {
+                weights,
+                explain,
+                pick,
+                force,
+                docs_format: DocsFormat::for_stdout(raw, color),
+            };
+            let query = expand_alias(&query, &config.aliases);
+            commands::lucky::execute(&client, query, options).await?;
         }
-        Commands::GetDocs { id } => {
-            commands::get_docs::execute(&client, id).await?;
+        Commands::Resolve {
+            input,
+            // Resolved with the config file and environment into `settings`
+            format: _,
+        } => {
+            let Session {
+                config,
+                settings,
+                client,
+                ..
+            } = Session::open(cli.config.as_deref(), &matches)?;
+            // A default format from the environment or config file may be meant
+            // for search, so only an explicit flag has to suit resolve
+            let format = match settings.format.source {
+                SettingSource::Flag => settings.format.value,
+                _ => settings
+                    .format
+                    .value
+                    .filter(|format| ResolveFormat::from_str(format).is_ok()),
+            };
+            let options = commands::resolve::ResolveOptions { format };
+            let input = expand_alias(&input, &config.aliases);
+            commands::resolve::execute(&client, input, options).await?;
+        }
+        Commands::Schema { target, fields } => {
+            commands::schema::execute(target, fields)?;
+        }
+        Commands::Completions {
+            shell,
+            static_script,
+        } => {
+            commands::completions::execute(shell, static_script)?;
+        }
+        Commands::Man { command, out_dir } => {
+            commands::man::execute(command, out_dir)?;
+        }
+        Commands::Shell {
+            // Resolved with the config file and environment into `settings`
+            sort_by: _,
+            limit: _,
+        } => {
+            let Session {
+                config,
+                settings,
+                client,
+            } = Session::open(cli.config.as_deref(), &matches)?;
+            let color = ColorChoice::from_str(&settings.color.value)?;
+            let options = commands::shell::ShellOptions {
+                sort_by: settings.sort_by.value,
+                limit: settings.limit.value,
+                aliases: config.aliases,
+                history_path: default_shell_history_path(),
+                docs_format: DocsFormat::for_stdout(false, color),
+            };
+            commands::shell::execute(&client, options).await?;
         }
-        Commands::Lucky { query } => {
-            commands::lucky::execute(&client, query).await?;
+        Commands::Tui { query } => {
+            let Session { client, .. } = Session::open(cli.config.as_deref(), &matches)?;
+            commands::tui::execute(&client, query).await?;
         }
+        Commands::Alias { action } => match action {
+            AliasAction::Add { name, id } => {
+                commands::alias::add(config_path.as_deref(), &name, &id)?
+            }
+            AliasAction::Remove { name } => commands::alias::remove(config_path.as_deref(), &name)?,
+            AliasAction::List => {
+                let config = ConfigFile::load(cli.config.as_deref())?;
+                commands::alias::list(&config.aliases)?
+            }
+        },
+        Commands::History { action } => match action {
+            HistoryAction::List { limit } => {
+                commands::history::list(history_path.as_deref(), limit)?
+            }
+            HistoryAction::Search { term } => {
+                commands::history::search(history_path.as_deref(), &term)?
+            }
+            HistoryAction::Clear => commands::history::clear(history_path.as_deref())?,
+        },
+        Commands::Config { action } => match action {
+            ConfigAction::Show => {
+                let config = ConfigFile::load(cli.config.as_deref())?;
+                let settings = Settings::resolve(cli::overrides(&matches)?, &config)?;
+                commands::config::show(&settings, config_path.as_deref())?
+            }
+            ConfigAction::Path => commands::config::path(config_path.as_deref())?,
+        },
     }
 
     Ok(())
@@ -0,0 +1,132 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::models::search::SearchResult;
+
+/// Version of the envelope layout; bump when fields change incompatibly
+pub const ENVELOPE_SCHEMA_VERSION: u32 = 1;
+
+/// Search results wrapped with the query and options that produced them,
+/// so archived output stays self-describing.
+///
+/// `R` is a full `SearchResult`, or a JSON object when `--fields` narrowed the output.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchEnvelope<R = SearchResult> {
+    pub schema_version: u32,
+    pub query: String,
+    pub sort_by: String,
+    /// Relevance weights; present when `sortBy` is "relevance"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weights: Option<EnvelopeWeights>,
+    /// Where results without a sort value went ("first" or "last"); present for field sorts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub missing: Option<String>,
+    /// Whether text fields were compared case-insensitively; present for field sorts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_case: Option<bool>,
+    pub filters: EnvelopeFilters,
+    pub limit: Option<usize>,
+    pub page: Option<u32>,
+    /// RFC 3339 timestamp of when the results were fetched
+    pub fetched_at: String,
+    /// Number of results in this envelope
+    pub count: usize,
+    /// Total matches reported by the API, when known
+    pub total: Option<u64>,
+    pub results: Vec<R>,
+}
+
+/// Weight of each component in the relevance score
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EnvelopeWeights {
+    pub name: f64,
+    pub trust: f64,
+    pub stars: f64,
+    pub snippets: f64,
+}
+
+/// Filters that were applied to the results; unset filters are omitted
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvelopeFilters {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_stars: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_trust: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_snippets: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Date in YYYY-MM-DD form
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_since: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_envelope() -> SearchEnvelope {
+        SearchEnvelope {
+            schema_version: ENVELOPE_SCHEMA_VERSION,
+            query: "react".to_string(),
+            sort_by: "stars".to_string(),
+            weights: None,
+            missing: Some("last".to_string()),
+            ignore_case: Some(false),
+            filters: EnvelopeFilters {
+                min_stars: Some(100),
+                ..Default::default()
+            },
+            limit: Some(1),
+            page: None,
+            fetched_at: "2025-01-15T10:00:00Z".to_string(),
+            count: 1,
+            total: Some(12),
+            results: vec![SearchResult {
+                id: "/facebook/react".to_string(),
+                title: Some("React".to_string()),
+                stars: Some(220000),
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_envelope_serializes_camel_case() {
+        let json = serde_json::to_value(create_test_envelope()).unwrap();
+
+        assert_eq!(json["schemaVersion"], 1);
+        assert_eq!(json["sortBy"], "stars");
+        assert_eq!(json["fetchedAt"], "2025-01-15T10:00:00Z");
+        assert_eq!(json["ignoreCase"], false);
+        assert_eq!(json["results"][0]["id"], "/facebook/react");
+    }
+
+    #[test]
+    fn test_envelope_omits_unset_filters() {
+        let json = serde_json::to_value(create_test_envelope()).unwrap();
+
+        assert_eq!(json["filters"], serde_json::json!({"minStars": 100}));
+    }
+
+    #[test]
+    fn test_envelope_omits_weights_for_field_sorts() {
+        let json = serde_json::to_value(create_test_envelope()).unwrap();
+
+        assert!(json.get("weights").is_none());
+        assert_eq!(json["missing"], "last");
+    }
+
+    #[test]
+    fn test_envelope_roundtrip() {
+        let json = serde_json::to_string(&create_test_envelope()).unwrap();
+
+        let envelope: SearchEnvelope = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(envelope.query, "react");
+        assert_eq!(envelope.total, Some(12));
+        assert_eq!(envelope.results[0].stars, Some(220000));
+    }
+}
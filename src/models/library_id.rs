@@ -0,0 +1,299 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::Result;
+
+/// Hosts whose URL paths map directly onto Context7 library IDs
+const CONTEXT7_HOSTS: [&str; 2] = ["context7.com", "www.context7.com"];
+const GITHUB_HOSTS: [&str; 2] = ["github.com", "www.github.com"];
+
+/// A validated Context7 library ID such as `/vercel/next.js` or `/vercel/next.js/v14.3.0`.
+///
+/// Accepts IDs with or without the leading slash, Context7 URLs
+/// (`https://context7.com/owner/repo`) and GitHub URLs
+/// (`https://github.com/owner/repo`), and always displays in canonical
+/// `/owner/repo[/version]` form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LibraryId {
+    owner: String,
+    repo: String,
+    version: Option<String>,
+}
+
+impl LibraryId {
+    /// Parse and validate a library ID, Context7 URL or GitHub URL.
+    ///
+    /// # Arguments
+    /// * `input` - User-supplied library reference
+    ///
+    /// # Returns
+    /// * `Ok(LibraryId)` - If the input names an owner, repository and optional version
+    /// * `Err` - If the input is empty, has the wrong number of segments, or contains
+    ///   characters that are not allowed in an ID
+    pub fn parse(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            anyhow::bail!("Library ID cannot be empty");
+        }
+
+        if let Some((host, path)) = split_url(trimmed) {
+            let host = host.to_lowercase();
+            if CONTEXT7_HOSTS.contains(&host.as_str()) {
+                return Self::from_segments(trimmed, context7_segments(path));
+            }
+            if GITHUB_HOSTS.contains(&host.as_str()) {
+                // Only owner/repo identify the library; /tree/main etc. are ignored
+                let mut segments = path_segments(path);
+                segments.truncate(2);
+                if let Some(repo) = segments.get_mut(1) {
+                    *repo = repo.strip_suffix(".git").unwrap_or(repo);
+                }
+                return Self::from_segments(trimmed, segments);
+            }
+            anyhow::bail!(
+                "Unsupported URL '{}'. Only context7.com and github.com URLs can be used as library IDs",
+                trimmed
+            );
+        }
+
+        let path = trimmed.strip_prefix('/').unwrap_or(trimmed);
+        Self::from_segments(trimmed, path.split('/').collect())
+    }
+
+    /// Interpret a free-text query as a library ID only when it is explicitly one:
+    /// a path with a leading slash or a supported URL.
+    ///
+    /// # Returns
+    /// * `Some(LibraryId)` - If the query is an explicit, valid ID
+    /// * `None` - If the query should be treated as a search instead
+    pub fn from_query(query: &str) -> Option<Self> {
+        let query = query.trim();
+        if query.starts_with('/') || split_url(query).is_some() {
+            Self::parse(query).ok()
+        } else {
+            None
+        }
+    }
+
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    pub fn repo(&self) -> &str {
+        &self.repo
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// The ID without its leading slash, as used in API URL paths
+    pub fn as_path(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{}/{}/{}", self.owner, self.repo, version),
+            None => format!("{}/{}", self.owner, self.repo),
+        }
+    }
+
+    fn from_segments(input: &str, segments: Vec<&str>) -> Result<Self> {
+        if !(2..=3).contains(&segments.len()) {
+            anyhow::bail!(
+                "Invalid library ID '{}'. Expected /owner/repo or /owner/repo/version",
+                input
+            );
+        }
+        for segment in &segments {
+            validate_segment(input, segment)?;
+        }
+
+        Ok(Self {
+            owner: segments[0].to_string(),
+            repo: segments[1].to_string(),
+            version: segments.get(2).map(|v| v.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for LibraryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}", self.as_path())
+    }
+}
+
+impl FromStr for LibraryId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+/// Split `https://host/path` into host and path, ignoring any query or fragment.
+fn split_url(input: &str) -> Option<(&str, &str)> {
+    let rest = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))?;
+    let rest = rest.split(['?', '#']).next().unwrap_or(rest);
+    Some(rest.split_once('/').unwrap_or((rest, "")))
+}
+
+/// Non-empty segments of a URL path.
+fn path_segments(path: &str) -> Vec<&str> {
+    path.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+/// Segments of a context7.com URL path, without the API prefix or `llms.txt` suffix.
+fn context7_segments(path: &str) -> Vec<&str> {
+    let mut segments = path_segments(path);
+    if segments.starts_with(&["api", "v1"]) {
+        segments.drain(..2);
+    }
+    if segments.last() == Some(&"llms.txt") {
+        segments.pop();
+    }
+    segments
+}
+
+/// Reject empty, relative (`.`/`..`) or otherwise unsafe path segments.
+fn validate_segment(input: &str, segment: &str) -> Result<()> {
+    if segment.is_empty() {
+        anyhow::bail!(
+            "Invalid library ID '{}'. Owner, repository and version cannot be empty",
+            input
+        );
+    }
+    if segment == "." || segment == ".." {
+        anyhow::bail!(
+            "Invalid library ID '{}'. Relative path segments are not allowed",
+            input
+        );
+    }
+    if let Some(c) = segment
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')))
+    {
+        anyhow::bail!(
+            "Invalid library ID '{}'. Character '{}' is not allowed; use letters, digits, '.', '-' or '_'",
+            input,
+            c.escape_default()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> LibraryId {
+        LibraryId::parse(input).unwrap()
+    }
+
+    #[test]
+    fn test_parse_with_and_without_leading_slash() {
+        assert_eq!(parse("/fastapi/fastapi"), parse("fastapi/fastapi"));
+        assert_eq!(parse("fastapi/fastapi").to_string(), "/fastapi/fastapi");
+    }
+
+    #[test]
+    fn test_parse_trims_whitespace() {
+        assert_eq!(parse("  /vercel/next.js\n").to_string(), "/vercel/next.js");
+    }
+
+    #[test]
+    fn test_parse_with_version() {
+        let id = parse("/vercel/next.js/v14.3.0");
+        assert_eq!(id.owner(), "vercel");
+        assert_eq!(id.repo(), "next.js");
+        assert_eq!(id.version(), Some("v14.3.0"));
+        assert_eq!(id.as_path(), "vercel/next.js/v14.3.0");
+    }
+
+    #[test]
+    fn test_parse_context7_urls() {
+        assert_eq!(
+            parse("https://context7.com/vercel/next.js").to_string(),
+            "/vercel/next.js"
+        );
+        assert_eq!(
+            parse("https://context7.com/vercel/next.js/llms.txt?tokens=1000").to_string(),
+            "/vercel/next.js"
+        );
+        assert_eq!(
+            parse("https://context7.com/api/v1/fastapi/fastapi").to_string(),
+            "/fastapi/fastapi"
+        );
+    }
+
+    #[test]
+    fn test_parse_github_urls() {
+        assert_eq!(
+            parse("https://github.com/fastapi/fastapi").to_string(),
+            "/fastapi/fastapi"
+        );
+        assert_eq!(
+            parse("https://github.com/vercel/next.js.git").to_string(),
+            "/vercel/next.js"
+        );
+        assert_eq!(
+            parse("https://www.github.com/tokio-rs/tokio/tree/master/tokio").to_string(),
+            "/tokio-rs/tokio"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_path_traversal_and_query_strings() {
+        assert!(LibraryId::parse("../search?query=x").is_err());
+        assert!(LibraryId::parse("/owner/..").is_err());
+        assert!(LibraryId::parse("/owner/repo?x=1").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_wrong_segment_counts() {
+        let error_msg = LibraryId::parse("react").unwrap_err().to_string();
+        assert!(error_msg.contains("Expected /owner/repo"));
+        assert!(LibraryId::parse("/a/b/c/d").is_err());
+        assert!(LibraryId::parse("/owner//repo").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_and_inner_whitespace() {
+        assert!(
+            LibraryId::parse("   ")
+                .unwrap_err()
+                .to_string()
+                .contains("cannot be empty")
+        );
+        let error_msg = LibraryId::parse("/my owner/repo").unwrap_err().to_string();
+        assert!(error_msg.contains("Character ' ' is not allowed"));
+    }
+
+    #[test]
+    fn test_parse_rejects_other_hosts() {
+        let error_msg = LibraryId::parse("https://example.com/owner/repo")
+            .unwrap_err()
+            .to_string();
+        assert!(error_msg.contains("Unsupported URL"));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let id: LibraryId = "/pallets/flask".parse().unwrap();
+        assert_eq!(id.repo(), "flask");
+    }
+
+    #[test]
+    fn test_from_query_only_accepts_explicit_ids() {
+        assert_eq!(
+            LibraryId::from_query("/fastapi/fastapi"),
+            Some(parse("/fastapi/fastapi"))
+        );
+        assert_eq!(
+            LibraryId::from_query("https://github.com/fastapi/fastapi"),
+            Some(parse("/fastapi/fastapi"))
+        );
+        assert_eq!(LibraryId::from_query("fastapi/fastapi"), None);
+        assert_eq!(LibraryId::from_query("react"), None);
+        assert_eq!(LibraryId::from_query("/not valid/x"), None);
+    }
+}
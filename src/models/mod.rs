@@ -1,3 +1,9 @@
+pub mod envelope;
+pub mod history;
+pub mod library_id;
 pub mod search;
 
-pub use search::SearchResponse;
+pub use envelope::{ENVELOPE_SCHEMA_VERSION, EnvelopeFilters, EnvelopeWeights, SearchEnvelope};
+pub use history::HistoryEntry;
+pub use library_id::LibraryId;
+pub use search::{PageInfo, SearchResponse};
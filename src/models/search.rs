@@ -1,11 +1,39 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct SearchResponse {
     pub results: Vec<SearchResult>,
+    /// Paging metadata, when the API reports any
+    #[serde(flatten)]
+    pub page_info: PageInfo,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+/// Paging and total-count metadata returned alongside search results.
+///
+/// Every field is optional because the API may omit any of them.
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfo {
+    pub page: Option<u32>,
+    #[serde(alias = "pageSize", alias = "limit")]
+    pub per_page: Option<u32>,
+    #[serde(alias = "pageCount")]
+    pub total_pages: Option<u32>,
+    #[serde(alias = "totalCount", alias = "totalResults")]
+    pub total: Option<u64>,
+    pub has_more: Option<bool>,
+}
+
+impl PageInfo {
+    /// Whether pages after `page` exist, or `None` if the API didn't say.
+    pub fn has_more_after(&self, page: u32) -> Option<bool> {
+        self.has_more
+            .or_else(|| self.total_pages.map(|total_pages| page < total_pages))
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone)]
 pub struct SearchResult {
     pub branch: Option<String>,
     pub description: Option<String>,
@@ -140,17 +168,10 @@ mod tests {
     fn test_search_result_serialization_uses_camel_case() {
         let result = SearchResult {
             id: "/test/lib".to_string(),
-            branch: None,
-            description: None,
             last_update_date: Some("2025-01-01".to_string()),
-            stars: None,
-            state: None,
-            title: None,
             total_pages: Some(10),
-            total_snippets: None,
-            total_tokens: None,
             trust_score: Some(8.0),
-            versions: None,
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -184,6 +205,46 @@ mod tests {
         assert_eq!(response.results[1].stars, Some(50));
     }
 
+    #[test]
+    fn test_search_response_without_page_info() {
+        let mock_json = r#"{"results": [{"id": "/first/lib"}]}"#;
+
+        let response: SearchResponse = serde_json::from_str(mock_json).unwrap();
+
+        assert_eq!(response.page_info, PageInfo::default());
+        assert_eq!(response.page_info.has_more_after(1), None);
+    }
+
+    #[test]
+    fn test_search_response_page_info() {
+        let mock_json = r#"{
+            "results": [{"id": "/first/lib"}],
+            "page": 2,
+            "pageSize": 10,
+            "totalPages": 3,
+            "totalCount": 25
+        }"#;
+
+        let response: SearchResponse = serde_json::from_str(mock_json).unwrap();
+
+        assert_eq!(response.page_info.page, Some(2));
+        assert_eq!(response.page_info.per_page, Some(10));
+        assert_eq!(response.page_info.total_pages, Some(3));
+        assert_eq!(response.page_info.total, Some(25));
+        assert_eq!(response.page_info.has_more_after(2), Some(true));
+        assert_eq!(response.page_info.has_more_after(3), Some(false));
+    }
+
+    #[test]
+    fn test_page_info_has_more_flag_wins() {
+        let page_info = PageInfo {
+            has_more: Some(false),
+            total_pages: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(page_info.has_more_after(1), Some(false));
+    }
+
     #[test]
     fn test_search_response_empty_results() {
         let mock_json = r#"{"results": []}"#;
@@ -196,16 +257,9 @@ mod tests {
         let original = SearchResult {
             id: "/clone/test".to_string(),
             title: Some("Clone Test".to_string()),
-            description: None,
-            branch: None,
-            state: None,
             stars: Some(42),
-            last_update_date: None,
-            total_pages: None,
-            total_snippets: None,
-            total_tokens: None,
             trust_score: Some(7.5),
-            versions: None,
+            ..Default::default()
         };
 
         let cloned = original.clone();
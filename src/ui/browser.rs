@@ -0,0 +1,635 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+
+use crate::core::docs::{code_blocks, matching_lines};
+use crate::core::sorting::{SortField, sort_search_results};
+use crate::models::search::SearchResult;
+
+/// Key summary shown in the status line when there is nothing else to report
+const KEY_HELP: &str =
+    "Enter docs  Tab pane  / search  s/S sort  y copy code  w save  n/N match  q quit";
+
+/// Pane that receives navigation keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Focus {
+    Results,
+    Docs,
+}
+
+/// What the prompt line is collecting
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PromptKind {
+    /// A new query for the results pane
+    Search,
+    /// A term to find in the docs pane
+    Find,
+}
+
+/// What the event loop should do after a key press
+#[derive(Debug, PartialEq)]
+pub enum Action {
+    Continue,
+    Quit,
+    /// Run a new search for the query
+    Search(String),
+    /// Fetch documentation for the library ID
+    LoadDocs(String),
+    /// Put the text on the clipboard
+    Copy(String),
+    /// Write the shown documentation to disk
+    Save,
+}
+
+/// Documentation shown in the right-hand pane
+struct Docs {
+    id: String,
+    body: String,
+    lines: Vec<String>,
+}
+
+/// State of the full-screen browser: results, shown docs and the prompt line
+pub struct Browser {
+    query: String,
+    /// Results in the order the API ranked them
+    ranked: Vec<SearchResult>,
+    /// Results in display order
+    results: Vec<SearchResult>,
+    /// `None` keeps the order the API returned
+    sort: Option<SortField>,
+    selected: usize,
+    focus: Focus,
+    docs: Option<Docs>,
+    scroll: usize,
+    /// Rows available for documentation at the last render
+    docs_height: usize,
+    prompt: Option<(PromptKind, String)>,
+    find: String,
+    matches: Vec<usize>,
+    status: String,
+}
+
+impl Browser {
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            ranked: Vec::new(),
+            results: Vec::new(),
+            sort: None,
+            selected: 0,
+            focus: Focus::Results,
+            docs: None,
+            scroll: 0,
+            docs_height: 0,
+            prompt: None,
+            find: String::new(),
+            matches: Vec::new(),
+            status: String::new(),
+        }
+    }
+
+    /// Replace the listed results with those of a new search.
+    pub fn set_results(&mut self, query: &str, results: Vec<SearchResult>) {
+        self.query = query.to_string();
+        self.ranked = results;
+        self.selected = 0;
+        self.apply_sort();
+        self.status = format!("{} results for '{}'", self.results.len(), query);
+    }
+
+    /// Show documentation for a library in the docs pane and focus it.
+    pub fn show_docs(&mut self, id: &str, body: String) {
+        self.docs = Some(Docs {
+            id: id.to_string(),
+            lines: body.lines().map(str::to_string).collect(),
+            body,
+        });
+        self.scroll = 0;
+        self.focus = Focus::Docs;
+        self.matches = self
+            .docs_lines()
+            .map_or_else(Vec::new, |lines| matching_lines(lines, &self.find));
+        self.status = format!("Loaded {}", id);
+    }
+
+    /// ID and text of the shown documentation.
+    pub fn docs(&self) -> Option<(&str, &str)> {
+        self.docs
+            .as_ref()
+            .map(|docs| (docs.id.as_str(), docs.body.as_str()))
+    }
+
+    pub fn set_status(&mut self, status: impl Into<String>) {
+        self.status = status.into();
+    }
+
+    fn docs_lines(&self) -> Option<&[String]> {
+        self.docs.as_ref().map(|docs| docs.lines.as_slice())
+    }
+
+    fn selected_result(&self) -> Option<&SearchResult> {
+        self.results.get(self.selected)
+    }
+
+    fn apply_sort(&mut self) {
+        let selected_id = self.selected_result().map(|result| result.id.clone());
+        self.results = match self.sort {
+            Some(field) => sort_search_results(self.ranked.clone(), field),
+            None => self.ranked.clone(),
+        };
+        // Keep the highlighted library selected when the order changes
+        self.selected = selected_id
+            .and_then(|id| self.results.iter().position(|result| result.id == id))
+            .unwrap_or(0);
+    }
+
+    /// Step through the API's order and every sort field, forwards or backwards.
+    fn cycle_sort(&mut self, forward: bool) {
+        let orders: Vec<Option<SortField>> = std::iter::once(None)
+            .chain(SortField::ALL.into_iter().map(Some))
+            .collect();
+        let current = orders
+            .iter()
+            .position(|order| *order == self.sort)
+            .unwrap_or(0);
+        let next = if forward {
+            (current + 1) % orders.len()
+        } else {
+            (current + orders.len() - 1) % orders.len()
+        };
+        self.sort = orders[next];
+        self.apply_sort();
+        self.status = format!("Sorted by {}", self.sort_name());
+    }
+
+    fn sort_name(&self) -> &'static str {
+        self.sort.map_or("api order", |field| field.name())
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.results.is_empty() {
+            return;
+        }
+        let last = self.results.len() - 1;
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.docs_lines().map_or(0, |lines| {
+            lines.len().saturating_sub(self.docs_height.max(1))
+        })
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(delta)
+            .min(self.max_scroll());
+    }
+
+    fn scroll_to(&mut self, line: usize) {
+        self.scroll = line.min(self.max_scroll());
+    }
+
+    fn page(&self) -> isize {
+        self.docs_height.max(1) as isize
+    }
+
+    /// Jump to the next (or previous) line matching the find term, wrapping around.
+    fn jump_to_match(&mut self, forward: bool) {
+        let target = if forward {
+            self.matches
+                .iter()
+                .find(|line| **line > self.scroll)
+                .or(self.matches.first())
+        } else {
+            self.matches
+                .iter()
+                .rev()
+                .find(|line| **line < self.scroll)
+                .or(self.matches.last())
+        };
+        self.show_match(target.copied());
+    }
+
+    /// Find a new term, starting from the line at the top of the view.
+    fn start_find(&mut self, term: String) {
+        self.find = term;
+        self.matches = self
+            .docs_lines()
+            .map_or_else(Vec::new, |lines| matching_lines(lines, &self.find));
+        let target = self
+            .matches
+            .iter()
+            .find(|line| **line >= self.scroll)
+            .or(self.matches.first());
+        self.show_match(target.copied());
+    }
+
+    fn show_match(&mut self, line: Option<usize>) {
+        let Some(line) = line else {
+            self.status = if self.find.is_empty() {
+                "Press / in the docs pane to find text".to_string()
+            } else {
+                format!("No matches for '{}'", self.find)
+            };
+            return;
+        };
+        self.scroll_to(line);
+        let position = self.matches.iter().position(|m| *m == line).unwrap_or(0);
+        self.status = format!(
+            "Match {}/{} for '{}'",
+            position + 1,
+            self.matches.len(),
+            self.find
+        );
+    }
+
+    /// The first code block that ends at or below the top of the docs view.
+    fn code_block_in_view(&self) -> Option<(usize, String)> {
+        code_blocks(self.docs_lines()?)
+            .into_iter()
+            .find(|block| block.end >= self.scroll)
+            .map(|block| (block.start, block.code))
+    }
+
+    /// Apply a key press to the browser.
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        if ctrl && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+        if self.prompt.is_some() {
+            return self.handle_prompt_key(key);
+        }
+
+        match (key.code, self.focus) {
+            (KeyCode::Char('q'), _) => return Action::Quit,
+            (KeyCode::Tab, Focus::Results) if self.docs.is_some() => self.focus = Focus::Docs,
+            (KeyCode::Tab | KeyCode::Esc, Focus::Docs) => self.focus = Focus::Results,
+            (KeyCode::Char('/'), Focus::Results) => {
+                self.prompt = Some((PromptKind::Search, String::new()))
+            }
+            (KeyCode::Char('/'), Focus::Docs) => {
+                self.prompt = Some((PromptKind::Find, String::new()))
+            }
+            (KeyCode::Char('s'), _) => self.cycle_sort(true),
+            (KeyCode::Char('S'), _) => self.cycle_sort(false),
+            (KeyCode::Char('n'), _) => self.jump_to_match(true),
+            (KeyCode::Char('N'), _) => self.jump_to_match(false),
+            (KeyCode::Char('y'), _) => match self.code_block_in_view() {
+                Some((start, code)) => {
+                    self.status = format!("Copied the code block at line {}", start + 1);
+                    return Action::Copy(code);
+                }
+                None => self.status = "No code block in view".to_string(),
+            },
+            (KeyCode::Char('w'), _) => {
+                if self.docs.is_some() {
+                    return Action::Save;
+                }
+                self.status = "Load docs with Enter before saving".to_string();
+            }
+            (KeyCode::Enter, Focus::Results) => {
+                if let Some(result) = self.selected_result() {
+                    return Action::LoadDocs(result.id.clone());
+                }
+            }
+            (KeyCode::Up | KeyCode::Char('k'), Focus::Results) => self.move_selection(-1),
+            (KeyCode::Down | KeyCode::Char('j'), Focus::Results) => self.move_selection(1),
+            (KeyCode::Home | KeyCode::Char('g'), Focus::Results) => self.selected = 0,
+            (KeyCode::End | KeyCode::Char('G'), Focus::Results) => {
+                self.selected = self.results.len().saturating_sub(1)
+            }
+            (KeyCode::Up | KeyCode::Char('k'), Focus::Docs) => self.scroll_by(-1),
+            (KeyCode::Down | KeyCode::Char('j'), Focus::Docs) => self.scroll_by(1),
+            (KeyCode::PageUp | KeyCode::Char('b'), Focus::Docs) => self.scroll_by(-self.page()),
+            (KeyCode::PageDown | KeyCode::Char(' '), Focus::Docs) => self.scroll_by(self.page()),
+            (KeyCode::Home | KeyCode::Char('g'), Focus::Docs) => self.scroll_to(0),
+            (KeyCode::End | KeyCode::Char('G'), Focus::Docs) => self.scroll_to(usize::MAX),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    fn handle_prompt_key(&mut self, key: KeyEvent) -> Action {
+        let Some((kind, text)) = self.prompt.as_mut() else {
+            return Action::Continue;
+        };
+        match key.code {
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) => text.push(c),
+            KeyCode::Enter => {
+                let (kind, text) = (*kind, text.trim().to_string());
+                self.prompt = None;
+                match kind {
+                    PromptKind::Search if !text.is_empty() => return Action::Search(text),
+                    PromptKind::Search => {}
+                    PromptKind::Find => self.start_find(text),
+                }
+            }
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Draw both panes and the status line.
+    pub fn render(&mut self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+                .areas(main);
+
+        let focused = Style::default().fg(Color::Cyan);
+        let pane_style = |focus: Focus| {
+            if self.focus == focus {
+                focused
+            } else {
+                Style::default()
+            }
+        };
+
+        let items: Vec<ListItem> = self
+            .results
+            .iter()
+            .map(|result| {
+                let mut spans = vec![Span::raw(result.id.clone())];
+                if let Some(stars) = result.stars {
+                    spans.push(Span::styled(
+                        format!("  ★{}", stars),
+                        Style::default().add_modifier(Modifier::DIM),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::bordered()
+                    .title(format!(" {} (sort: {}) ", self.query, self.sort_name()))
+                    .border_style(pane_style(Focus::Results)),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut list_state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, left, &mut list_state);
+
+        let docs_block = Block::bordered()
+            .title(match &self.docs {
+                Some(docs) => format!(" {} ", docs.id),
+                None => " Docs ".to_string(),
+            })
+            .border_style(pane_style(Focus::Docs));
+        self.docs_height = docs_block.inner(right).height as usize;
+        self.scroll = self.scroll.min(self.max_scroll());
+        let text: Vec<Line> = match self.docs_lines() {
+            Some(lines) => lines
+                .iter()
+                .skip(self.scroll)
+                .take(self.docs_height)
+                .map(|line| highlight_matches(line, &self.find))
+                .collect(),
+            None => vec![Line::styled(
+                "Select a library and press Enter to load its docs",
+                Style::default().add_modifier(Modifier::DIM),
+            )],
+        };
+        frame.render_widget(Paragraph::new(text).block(docs_block), right);
+
+        let status_line = match &self.prompt {
+            Some((PromptKind::Search, text)) => Line::raw(format!("search: {}", text)),
+            Some((PromptKind::Find, text)) => Line::raw(format!("/{}", text)),
+            None if self.status.is_empty() => {
+                Line::styled(KEY_HELP, Style::default().add_modifier(Modifier::DIM))
+            }
+            None => Line::raw(self.status.clone()),
+        };
+        frame.render_widget(Paragraph::new(status_line), status);
+    }
+}
+
+/// Style every occurrence of the find term in a docs line, ignoring ASCII case.
+fn highlight_matches<'a>(line: &'a str, term: &str) -> Line<'a> {
+    if term.is_empty() {
+        return Line::raw(line);
+    }
+    let haystack = line.to_ascii_lowercase();
+    let needle = term.to_ascii_lowercase();
+    let highlight = Style::default().bg(Color::Yellow).fg(Color::Black);
+
+    let mut spans = Vec::new();
+    let mut position = 0;
+    while let Some(offset) = haystack[position..].find(&needle) {
+        let start = position + offset;
+        let end = start + needle.len();
+        spans.push(Span::raw(&line[position..start]));
+        spans.push(Span::styled(&line[start..end], highlight));
+        position = end;
+    }
+    spans.push(Span::raw(&line[position..]));
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn create_test_result(id: &str, stars: i64) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            stars: Some(stars),
+            ..Default::default()
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(browser: &mut Browser, text: &str) -> Action {
+        for c in text.chars() {
+            browser.handle_key(key(KeyCode::Char(c)));
+        }
+        browser.handle_key(key(KeyCode::Enter))
+    }
+
+    fn browser_with_results() -> Browser {
+        let mut browser = Browser::new();
+        browser.set_results(
+            "react",
+            vec![
+                create_test_result("/b/low", 10),
+                create_test_result("/a/high", 500),
+                create_test_result("/c/mid", 100),
+            ],
+        );
+        browser
+    }
+
+    fn browser_with_docs(body: &str) -> Browser {
+        let mut browser = browser_with_results();
+        browser.show_docs("/b/low", body.to_string());
+        browser.docs_height = 3;
+        browser
+    }
+
+    #[test]
+    fn test_enter_loads_selected_docs() {
+        let mut browser = browser_with_results();
+
+        browser.handle_key(key(KeyCode::Down));
+
+        assert_eq!(
+            browser.handle_key(key(KeyCode::Enter)),
+            Action::LoadDocs("/a/high".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sort_cycles_every_field_and_keeps_selection() {
+        let mut browser = browser_with_results();
+        browser.handle_key(key(KeyCode::Down));
+
+        browser.handle_key(key(KeyCode::Char('s')));
+
+        assert_eq!(browser.sort, Some(SortField::Stars));
+        assert_eq!(browser.results[0].id, "/a/high");
+        assert_eq!(browser.selected_result().unwrap().id, "/a/high");
+
+        for _ in 0..SortField::ALL.len() {
+            browser.handle_key(key(KeyCode::Char('s')));
+        }
+        assert_eq!(browser.sort, None);
+        assert_eq!(browser.results[0].id, "/b/low");
+
+        browser.handle_key(key(KeyCode::Char('S')));
+        assert_eq!(browser.sort, Some(SortField::LastUpdateDate));
+    }
+
+    #[test]
+    fn test_search_prompt_returns_query() {
+        let mut browser = browser_with_results();
+
+        browser.handle_key(key(KeyCode::Char('/')));
+
+        assert_eq!(
+            type_text(&mut browser, "vue"),
+            Action::Search("vue".to_string())
+        );
+        assert!(browser.prompt.is_none());
+    }
+
+    #[test]
+    fn test_prompt_swallows_command_keys_and_esc_cancels() {
+        let mut browser = browser_with_results();
+        browser.handle_key(key(KeyCode::Char('/')));
+
+        assert_eq!(
+            browser.handle_key(key(KeyCode::Char('q'))),
+            Action::Continue
+        );
+        browser.handle_key(key(KeyCode::Esc));
+
+        assert!(browser.prompt.is_none());
+        assert_eq!(browser.handle_key(key(KeyCode::Char('q'))), Action::Quit);
+    }
+
+    #[test]
+    fn test_docs_scrolling_is_clamped() {
+        let mut browser = browser_with_docs("1\n2\n3\n4\n5");
+
+        browser.handle_key(key(KeyCode::PageDown));
+        assert_eq!(browser.scroll, 2);
+        browser.handle_key(key(KeyCode::Down));
+        assert_eq!(browser.scroll, 2);
+        browser.handle_key(key(KeyCode::Char('g')));
+        assert_eq!(browser.scroll, 0);
+    }
+
+    #[test]
+    fn test_find_jumps_between_matches() {
+        let body = "intro\nInstall it\na\nb\nc\nd\ninstall again\ne\nf";
+        let mut browser = browser_with_docs(body);
+
+        browser.handle_key(key(KeyCode::Char('/')));
+        type_text(&mut browser, "install");
+
+        assert_eq!(browser.matches, vec![1, 6]);
+        assert_eq!(browser.scroll, 1);
+        browser.handle_key(key(KeyCode::Char('n')));
+        assert_eq!(browser.scroll, 6);
+        browser.handle_key(key(KeyCode::Char('n')));
+        assert_eq!(browser.scroll, 1);
+        browser.handle_key(key(KeyCode::Char('N')));
+        assert_eq!(browser.scroll, 6);
+    }
+
+    #[test]
+    fn test_copy_takes_first_code_block_in_view() {
+        let body = "```sh\nnpm i a\n```\ntext\n```js\nrun()\n```\nend";
+        let mut browser = browser_with_docs(body);
+
+        assert_eq!(
+            browser.handle_key(key(KeyCode::Char('y'))),
+            Action::Copy("npm i a".to_string())
+        );
+        browser.scroll_to(3);
+        assert_eq!(
+            browser.handle_key(key(KeyCode::Char('y'))),
+            Action::Copy("run()".to_string())
+        );
+    }
+
+    #[test]
+    fn test_save_requires_docs() {
+        let mut browser = browser_with_results();
+
+        assert_eq!(
+            browser.handle_key(key(KeyCode::Char('w'))),
+            Action::Continue
+        );
+        browser.show_docs("/b/low", "docs".to_string());
+        assert_eq!(browser.handle_key(key(KeyCode::Char('w'))), Action::Save);
+        assert_eq!(browser.docs(), Some(("/b/low", "docs")));
+    }
+
+    #[test]
+    fn test_highlight_matches_splits_spans() {
+        let line = highlight_matches("Use useState here", "usestate");
+
+        let texts: Vec<&str> = line
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(texts, vec!["Use ", "useState", " here"]);
+    }
+
+    #[test]
+    fn test_render_shows_results_and_docs() {
+        let mut browser = browser_with_docs("# Hello docs");
+        let mut terminal = Terminal::new(TestBackend::new(80, 10)).unwrap();
+
+        terminal.draw(|frame| browser.render(frame)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("/a/high"));
+        assert!(screen.contains("sort: api order"));
+        assert!(screen.contains("# Hello docs"));
+        assert_eq!(browser.docs_height, 7);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod browser;
+pub mod picker;
+pub mod terminal;
@@ -0,0 +1,389 @@
+use std::io::Write;
+
+use anyhow::Result;
+use crossterm::cursor::{MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::queue;
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::core::fuzzy::fuzzy_filter;
+use crate::models::search::SearchResult;
+use crate::ui::terminal::{Screen, TerminalGuard};
+
+/// Number of terminal rows reserved for the preview pane
+const PREVIEW_HEIGHT: usize = 6;
+
+/// Let the user fuzzy-find one of the search results in the terminal.
+///
+/// The picker draws on stderr in the alternate screen so stdout remains free
+/// for the documentation that is printed afterwards.
+///
+/// # Arguments
+/// * `results` - Candidates to choose from, in their initial display order
+///
+/// # Returns
+/// * `Ok(Some(index))` - Index into `results` of the chosen candidate
+/// * `Ok(None)` - If the user cancelled
+/// * `Err` - If stdin or stderr is not a terminal or the terminal could not be driven
+pub fn pick(results: &[SearchResult]) -> Result<Option<usize>> {
+    ensure_terminal()?;
+
+    let haystacks: Vec<String> = results.iter().map(match_text).collect();
+    let mut state = PickerState::new(fuzzy_filter("", &haystacks));
+
+    let _guard = TerminalGuard::enter(Screen::Stderr)?;
+    let mut out = std::io::stderr();
+
+    loop {
+        draw(&mut out, results, &mut state)?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match handle_key(&mut state, key) {
+            Action::Continue => {}
+            Action::QueryChanged => {
+                let matches = fuzzy_filter(&state.query, &haystacks);
+                state.set_matches(matches);
+            }
+            Action::Accept => return Ok(state.selected_index()),
+            Action::Cancel => return Ok(None),
+        }
+    }
+}
+
+/// Fail early when the picker cannot run because stdin or stderr is not a terminal.
+pub fn ensure_terminal() -> Result<()> {
+    crate::ui::terminal::ensure_terminal(Screen::Stderr)
+}
+
+/// Text a candidate is matched against: its ID followed by its title.
+fn match_text(result: &SearchResult) -> String {
+    format!("{} {}", result.id, result.title.as_deref().unwrap_or(""))
+}
+
+/// What the event loop should do after a key press
+#[derive(Debug, PartialEq)]
+enum Action {
+    Continue,
+    QueryChanged,
+    Accept,
+    Cancel,
+}
+
+/// Query text, matching candidates and cursor position of the picker
+struct PickerState {
+    query: String,
+    matches: Vec<usize>,
+    selected: usize,
+    scroll: usize,
+}
+
+impl PickerState {
+    fn new(matches: Vec<usize>) -> Self {
+        Self {
+            query: String::new(),
+            matches,
+            selected: 0,
+            scroll: 0,
+        }
+    }
+
+    fn set_matches(&mut self, matches: Vec<usize>) {
+        self.matches = matches;
+        self.selected = 0;
+        self.scroll = 0;
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.matches.get(self.selected).copied()
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let last = self.matches.len() - 1;
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    /// Adjust scrolling so the selected row stays within `height` visible rows.
+    fn scroll_into_view(&mut self, height: usize) {
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if height > 0 && self.selected >= self.scroll + height {
+            self.scroll = self.selected + 1 - height;
+        }
+    }
+}
+
+/// Apply a key press to the picker state.
+fn handle_key(state: &mut PickerState, key: KeyEvent) -> Action {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Enter => Action::Accept,
+        KeyCode::Esc => Action::Cancel,
+        KeyCode::Char('c') if ctrl => Action::Cancel,
+        KeyCode::Up => {
+            state.move_selection(-1);
+            Action::Continue
+        }
+        KeyCode::Char('p') if ctrl => {
+            state.move_selection(-1);
+            Action::Continue
+        }
+        KeyCode::Down => {
+            state.move_selection(1);
+            Action::Continue
+        }
+        KeyCode::Char('n') if ctrl => {
+            state.move_selection(1);
+            Action::Continue
+        }
+        KeyCode::Backspace => {
+            state.query.pop();
+            Action::QueryChanged
+        }
+        KeyCode::Char(c) if !ctrl => {
+            state.query.push(c);
+            Action::QueryChanged
+        }
+        _ => Action::Continue,
+    }
+}
+
+/// Render the prompt, candidate list and preview pane.
+fn draw(out: &mut impl Write, results: &[SearchResult], state: &mut PickerState) -> Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let width = columns as usize;
+    let list_height = (rows as usize).saturating_sub(PREVIEW_HEIGHT + 3);
+    state.scroll_into_view(list_height);
+
+    queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+    queue!(
+        out,
+        Print(fit(&format!("> {}", state.query), width)),
+        MoveTo(0, 1),
+        SetAttribute(Attribute::Dim),
+        Print(fit(
+            &format!(
+                "  {}/{}  (↑/↓ to move, Enter to select, Esc to cancel)",
+                state.matches.len(),
+                results.len()
+            ),
+            width
+        )),
+        SetAttribute(Attribute::Reset),
+    )?;
+
+    for (row, match_position) in (state.scroll..state.matches.len())
+        .take(list_height)
+        .enumerate()
+    {
+        let result = &results[state.matches[match_position]];
+        let line = fit(
+            &format!("  {}  {}", result.id, result.title.as_deref().unwrap_or("")),
+            width,
+        );
+        queue!(out, MoveTo(0, (row + 2) as u16))?;
+        if match_position == state.selected {
+            queue!(
+                out,
+                SetAttribute(Attribute::Reverse),
+                Print(line),
+                SetAttribute(Attribute::Reset)
+            )?;
+        } else {
+            queue!(out, Print(line))?;
+        }
+    }
+
+    let preview_top = (list_height + 2) as u16;
+    queue!(out, MoveTo(0, preview_top), Print("─".repeat(width)))?;
+    if let Some(index) = state.selected_index() {
+        for (offset, line) in preview_lines(&results[index])
+            .iter()
+            .take(PREVIEW_HEIGHT)
+            .enumerate()
+        {
+            queue!(
+                out,
+                MoveTo(0, preview_top + 1 + offset as u16),
+                Print(fit(line, width))
+            )?;
+        }
+    }
+
+    let cursor_column = (2 + state.query.width()).min(width.saturating_sub(1));
+    queue!(out, MoveTo(cursor_column as u16, 0), Show)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Lines shown in the preview pane for the highlighted candidate.
+fn preview_lines(result: &SearchResult) -> Vec<String> {
+    let metric = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    vec![
+        format!(
+            "{}  {}",
+            result.title.as_deref().unwrap_or("(untitled)"),
+            result.id
+        ),
+        result
+            .description
+            .as_deref()
+            .unwrap_or("No description")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" "),
+        format!(
+            "stars: {}  trust: {}  snippets: {}  tokens: {}",
+            metric(result.stars.map(|s| s.to_string())),
+            metric(result.trust_score.map(|t| format!("{:.1}", t))),
+            metric(result.total_snippets.map(|s| s.to_string())),
+            metric(result.total_tokens.map(|t| t.to_string())),
+        ),
+        format!(
+            "updated: {}  state: {}",
+            metric(result.last_update_date.clone()),
+            metric(result.state.clone())
+        ),
+    ]
+}
+
+/// Cut a line to the terminal width so it never wraps.
+fn fit(line: &str, width: usize) -> String {
+    let mut fitted = String::new();
+    let mut used = 0;
+    for c in line.chars() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            break;
+        }
+        fitted.push(c);
+    }
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_result(id: &str, title: Option<&str>) -> SearchResult {
+        SearchResult {
+            id: id.to_string(),
+            description: Some("A library\nspanning lines".to_string()),
+            last_update_date: Some("2025-01-15".to_string()),
+            stars: Some(1200),
+            state: Some("finalized".to_string()),
+            title: title.map(|s| s.to_string()),
+            total_snippets: Some(300),
+            trust_score: Some(8.25),
+            ..Default::default()
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_preview_lines_include_description_and_metrics() {
+        let lines = preview_lines(&create_test_result("/test/lib", Some("Test")));
+
+        assert_eq!(lines[0], "Test  /test/lib");
+        assert_eq!(lines[1], "A library spanning lines");
+        assert!(lines[2].contains("stars: 1200"));
+        assert!(lines[2].contains("trust: 8.2"));
+        assert!(lines[2].contains("tokens: -"));
+        assert!(lines[3].contains("state: finalized"));
+    }
+
+    #[test]
+    fn test_fit_counts_terminal_columns() {
+        assert_eq!(fit("React 日本語", 20), "React 日本語");
+        assert_eq!(fit("React 日本語", 9), "React 日");
+        assert_eq!(fit("🚀🚀", 3), "🚀");
+    }
+
+    #[test]
+    fn test_handle_key_typing_updates_query() {
+        let mut state = PickerState::new(vec![0, 1]);
+
+        assert_eq!(
+            handle_key(&mut state, key(KeyCode::Char('v'))),
+            Action::QueryChanged
+        );
+        assert_eq!(
+            handle_key(&mut state, key(KeyCode::Char('u'))),
+            Action::QueryChanged
+        );
+        handle_key(&mut state, key(KeyCode::Backspace));
+
+        assert_eq!(state.query, "v");
+    }
+
+    #[test]
+    fn test_handle_key_navigation_is_clamped() {
+        let mut state = PickerState::new(vec![4, 2, 7]);
+
+        handle_key(&mut state, key(KeyCode::Up));
+        assert_eq!(state.selected_index(), Some(4));
+
+        handle_key(&mut state, key(KeyCode::Down));
+        handle_key(&mut state, key(KeyCode::Down));
+        handle_key(&mut state, key(KeyCode::Down));
+        assert_eq!(state.selected_index(), Some(7));
+
+        assert_eq!(handle_key(&mut state, key(KeyCode::Enter)), Action::Accept);
+    }
+
+    #[test]
+    fn test_handle_key_cancel() {
+        let mut state = PickerState::new(vec![0]);
+
+        assert_eq!(handle_key(&mut state, key(KeyCode::Esc)), Action::Cancel);
+        assert_eq!(
+            handle_key(
+                &mut state,
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)
+            ),
+            Action::Cancel
+        );
+    }
+
+    #[test]
+    fn test_set_matches_resets_selection() {
+        let mut state = PickerState::new(vec![0, 1, 2]);
+        state.move_selection(2);
+
+        state.set_matches(vec![1]);
+
+        assert_eq!(state.selected_index(), Some(1));
+    }
+
+    #[test]
+    fn test_no_matches_selects_nothing() {
+        let mut state = PickerState::new(vec![]);
+        state.move_selection(1);
+        assert_eq!(state.selected_index(), None);
+    }
+
+    #[test]
+    fn test_scroll_into_view_follows_selection() {
+        let mut state = PickerState::new((0..20).collect());
+        state.move_selection(12);
+        state.scroll_into_view(5);
+        assert_eq!(state.scroll, 8);
+
+        state.move_selection(-10);
+        state.scroll_into_view(5);
+        assert_eq!(state.scroll, 2);
+    }
+}
@@ -0,0 +1,100 @@
+use std::io::{IsTerminal, Write};
+
+use anyhow::Result;
+use crossterm::cursor::{Hide, Show};
+use crossterm::execute;
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+
+/// Stream an interactive interface draws on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Screen {
+    /// For full-screen interfaces, which are the command's only output
+    Stdout,
+    /// For pickers, leaving stdout free for what is printed afterwards
+    Stderr,
+}
+
+impl Screen {
+    fn name(&self) -> &'static str {
+        match self {
+            Screen::Stdout => "stdout",
+            Screen::Stderr => "stderr",
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        match self {
+            Screen::Stdout => std::io::stdout().is_terminal(),
+            Screen::Stderr => std::io::stderr().is_terminal(),
+        }
+    }
+
+    fn writer(&self) -> Box<dyn Write> {
+        match self {
+            Screen::Stdout => Box::new(std::io::stdout()),
+            Screen::Stderr => Box::new(std::io::stderr()),
+        }
+    }
+}
+
+/// Fail early when an interactive interface cannot run: it reads keys from
+/// stdin and draws on `screen`, so both must be terminals. Otherwise escape
+/// codes would end up in a redirected file.
+pub fn ensure_terminal(screen: Screen) -> Result<()> {
+    check_terminals(std::io::stdin().is_terminal(), screen, screen.is_terminal())
+}
+
+fn check_terminals(
+    stdin_is_terminal: bool,
+    screen: Screen,
+    screen_is_terminal: bool,
+) -> Result<()> {
+    if !stdin_is_terminal {
+        anyhow::bail!("Interactive selection requires a terminal, but stdin is not a TTY");
+    }
+    if !screen_is_terminal {
+        anyhow::bail!(
+            "Interactive selection requires a terminal, but {} is not a TTY",
+            screen.name()
+        );
+    }
+    Ok(())
+}
+
+/// Puts the terminal into raw mode on the alternate screen, restoring it on drop
+pub struct TerminalGuard {
+    screen: Screen,
+}
+
+impl TerminalGuard {
+    pub fn enter(screen: Screen) -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(screen.writer(), EnterAlternateScreen, Hide)?;
+        Ok(Self { screen })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(self.screen.writer(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_terminals_requires_stdin() {
+        let err = check_terminals(false, Screen::Stdout, true).unwrap_err();
+        assert!(err.to_string().contains("stdin is not a TTY"));
+    }
+
+    #[test]
+    fn test_check_terminals_requires_screen() {
+        let err = check_terminals(true, Screen::Stdout, false).unwrap_err();
+        assert!(err.to_string().contains("stdout is not a TTY"));
+        assert!(check_terminals(true, Screen::Stderr, true).is_ok());
+    }
+}
@@ -132,3 +132,375 @@ fn test_lucky_command() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+/// Test that the interactive picker refuses to run without a terminal
+///
+/// The TTY check happens before any request, so this runs without network access.
+#[test]
+fn test_lucky_pick_requires_tty() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.arg("lucky").arg("react").arg("--pick").write_stdin("");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("stdin is not a TTY"));
+
+    Ok(())
+}
+
+/// Test that interactive search refuses to run without a terminal
+#[test]
+fn test_search_interactive_requires_tty() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.arg("search")
+        .arg("react")
+        .arg("--interactive")
+        .write_stdin("");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("stdin is not a TTY"));
+
+    Ok(())
+}
+
+/// Test that the full-screen browser refuses to run without a terminal
+#[test]
+fn test_tui_requires_tty() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.arg("tui").arg("react").write_stdin("");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("stdin is not a TTY"));
+
+    Ok(())
+}
+
+/// Write a config file unique to one test and return its path
+fn write_test_config(name: &str, contents: &str) -> std::io::Result<std::path::PathBuf> {
+    let path =
+        std::env::temp_dir().join(format!("context7-cli-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Test that config show reports each setting's source, honoring precedence
+#[test]
+fn test_config_show_precedence() -> Result<(), Box<dyn std::error::Error>> {
+    let config = write_test_config(
+        "precedence",
+        r#"
+default-profile = "work"
+timeout = 10
+
+[profiles.work]
+base-url = "https://context7.internal/api/v1"
+limit = 5
+"#,
+    )?;
+
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.env("CONTEXT7_CONFIG", &config)
+        .env("CONTEXT7_LIMIT", "7")
+        .env_remove("CONTEXT7_PROFILE")
+        .env_remove("CONTEXT7_BASE_URL")
+        .env_remove("CONTEXT7_TIMEOUT")
+        .env_remove("CONTEXT7_SORT_BY")
+        .args(["config", "show", "--timeout", "3"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("(profile 'work')"))
+        .stdout(predicate::str::is_match(r"timeout\s+3\s+\(flag\)")?)
+        .stdout(predicate::str::is_match(
+            r"limit\s+7\s+\(env CONTEXT7_LIMIT\)",
+        )?)
+        .stdout(predicate::str::is_match(r"sort-by\s+stars\s+\(default\)")?);
+
+    std::fs::remove_file(config)?;
+    Ok(())
+}
+
+/// Test that selecting an undefined profile fails with the available names
+#[test]
+fn test_unknown_profile_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let config = write_test_config("unknown-profile", "[profiles.work]\nlimit = 5\n")?;
+
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.arg("--config")
+        .arg(&config)
+        .args(["--profile", "home", "config", "show"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown profile 'home'"))
+        .stderr(predicate::str::contains("Available profiles: work"));
+
+    std::fs::remove_file(config)?;
+    Ok(())
+}
+
+/// Test that a config file with an unknown key is rejected, but doesn't stop
+/// commands that never read it
+#[test]
+fn test_broken_config_only_fails_commands_that_use_it() -> Result<(), Box<dyn std::error::Error>> {
+    let config = write_test_config("unknown-key", "base_url = \"https://example.com\"\n")?;
+
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.arg("--config").arg(&config).args(["config", "show"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown field `base_url`"));
+
+    for args in [["config", "path"], ["schema", "search"]] {
+        let mut cmd = Command::cargo_bin("context7-cli")?;
+        cmd.arg("--config").arg(&config).args(args);
+        cmd.assert().success();
+    }
+
+    std::fs::remove_file(config)?;
+    Ok(())
+}
+
+/// Test that offline mode reports uncached documentation instead of fetching it
+#[test]
+fn test_offline_without_cache_entry_fails() -> Result<(), Box<dyn std::error::Error>> {
+    let cache_dir =
+        std::env::temp_dir().join(format!("context7-cli-offline-{}", std::process::id()));
+
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.env("CONTEXT7_OFFLINE", "1")
+        .env("CONTEXT7_CACHE_DIR", &cache_dir)
+        .args(["get-docs", "/pallets/flask"]);
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("is not cached"));
+
+    Ok(())
+}
+
+/// Test that help lists the environment variable for every global option
+#[test]
+fn test_help_shows_env_names() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.arg("--help");
+
+    let mut assert = cmd.assert().success();
+    for name in [
+        "CONTEXT7_CONFIG",
+        "CONTEXT7_PROFILE",
+        "CONTEXT7_BASE_URL",
+        "CONTEXT7_API_KEY",
+        "CONTEXT7_TIMEOUT",
+        "CONTEXT7_CACHE_DIR",
+        "CONTEXT7_OFFLINE",
+    ] {
+        assert = assert.stdout(predicate::str::contains(format!("[env: {}", name)));
+    }
+
+    // --format belongs to the commands that have formats
+    for command in ["search", "resolve"] {
+        let mut cmd = Command::cargo_bin("context7-cli")?;
+        cmd.args([command, "--help"]);
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("[env: CONTEXT7_FORMAT"));
+    }
+
+    Ok(())
+}
+
+/// Test that tab completion for get-docs offers library IDs from the cache
+#[test]
+fn test_get_docs_completes_cached_ids() -> Result<(), Box<dyn std::error::Error>> {
+    let cache_dir =
+        std::env::temp_dir().join(format!("context7-cli-complete-{}", std::process::id()));
+    let docs_dir = cache_dir.join("docs").join("pallets");
+    std::fs::create_dir_all(&docs_dir)?;
+    std::fs::write(docs_dir.join("flask.txt"), "docs")?;
+    std::fs::write(docs_dir.join("flask@v3.0.txt"), "docs")?;
+
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.env("COMPLETE", "fish")
+        .env("CONTEXT7_CACHE_DIR", &cache_dir)
+        .env("XDG_DATA_HOME", cache_dir.join("data"))
+        .env_remove("CONTEXT7_CONFIG")
+        .env_remove("CONTEXT7_PROFILE")
+        .args(["--", "context7-cli", "get-docs", "/pal"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("/pallets/flask\tcached"))
+        .stdout(predicate::str::contains("/pallets/flask/v3.0\tcached"));
+
+    std::fs::remove_dir_all(cache_dir)?;
+    Ok(())
+}
+
+/// Test that fetched libraries are recorded in the history
+#[test]
+fn test_history_records_fetched_docs() -> Result<(), Box<dyn std::error::Error>> {
+    let base = std::env::temp_dir().join(format!("context7-cli-history-{}", std::process::id()));
+    let cache_dir = base.join("cache");
+    let data_dir = base.join("data");
+    let docs_dir = cache_dir.join("docs").join("pallets");
+    std::fs::create_dir_all(&docs_dir)?;
+    std::fs::write(docs_dir.join("flask.txt"), "# Flask")?;
+
+    let run = |args: &[&str]| -> Result<assert_cmd::assert::Assert, Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("context7-cli")?;
+        cmd.env("CONTEXT7_OFFLINE", "1")
+            .env("CONTEXT7_CACHE_DIR", &cache_dir)
+            .env("XDG_DATA_HOME", &data_dir)
+            .env_remove("CONTEXT7_CONFIG")
+            .env_remove("CONTEXT7_PROFILE")
+            .args(args);
+        Ok(cmd.assert())
+    };
+
+    run(&["get-docs", "/pallets/flask"])?.success();
+    run(&["history", "list"])?
+        .success()
+        .stdout(predicate::str::is_match(r"get-docs\s+/pallets/flask\n$").unwrap());
+    run(&["history", "search", "FLASK"])?
+        .success()
+        .stdout(predicate::str::contains("/pallets/flask"));
+    run(&["history", "clear"])?.success();
+    run(&["history", "list"])?
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    std::fs::remove_dir_all(base)?;
+    Ok(())
+}
+
+/// Test that docs are printed raw to a pipe and rendered with --color always
+#[test]
+fn test_get_docs_renders_markdown() -> Result<(), Box<dyn std::error::Error>> {
+    let base = std::env::temp_dir().join(format!("context7-cli-render-{}", std::process::id()));
+    let docs_dir = base.join("docs").join("pallets");
+    std::fs::create_dir_all(&docs_dir)?;
+    std::fs::write(
+        docs_dir.join("flask.txt"),
+        "# Flask\n\n```python\nimport flask\n```\n",
+    )?;
+
+    let run = |args: &[&str]| -> Result<assert_cmd::assert::Assert, Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("context7-cli")?;
+        cmd.env("CONTEXT7_OFFLINE", "1")
+            .env("CONTEXT7_CACHE_DIR", &base)
+            .env("XDG_DATA_HOME", base.join("data"))
+            .env_remove("CONTEXT7_COLOR")
+            .args(args);
+        Ok(cmd.assert())
+    };
+
+    run(&["get-docs", "/pallets/flask"])?
+        .success()
+        .stdout("# Flask\n\n```python\nimport flask\n```\n\n");
+    run(&["--color", "always", "get-docs", "/pallets/flask"])?
+        .success()
+        .stdout(predicate::str::contains("\x1b["))
+        .stdout(predicate::str::contains("```").not());
+    run(&["--color", "always", "get-docs", "/pallets/flask", "--raw"])?
+        .success()
+        .stdout(predicate::str::contains("\x1b[").not());
+    run(&["--color", "sometimes", "get-docs", "/pallets/flask"])?
+        .failure()
+        .stderr(predicate::str::contains("Invalid color choice"));
+
+    std::fs::remove_dir_all(base)?;
+    Ok(())
+}
+
+/// Test that aliases are saved to the config file and accepted as library IDs
+#[test]
+fn test_alias_add_and_use() -> Result<(), Box<dyn std::error::Error>> {
+    let base = std::env::temp_dir().join(format!("context7-cli-alias-{}", std::process::id()));
+    let cache_dir = base.join("cache");
+    let docs_dir = cache_dir.join("docs").join("pallets");
+    std::fs::create_dir_all(&docs_dir)?;
+    std::fs::write(docs_dir.join("flask.txt"), "# Flask docs")?;
+
+    let run = |args: &[&str]| -> Result<assert_cmd::assert::Assert, Box<dyn std::error::Error>> {
+        let mut cmd = Command::cargo_bin("context7-cli")?;
+        cmd.env("CONTEXT7_OFFLINE", "1")
+            .env("CONTEXT7_CACHE_DIR", &cache_dir)
+            .env("XDG_CONFIG_HOME", base.join("config"))
+            .env("XDG_DATA_HOME", base.join("data"))
+            .env_remove("CONTEXT7_CONFIG")
+            .env_remove("CONTEXT7_PROFILE")
+            .args(args);
+        Ok(cmd.assert())
+    };
+
+    run(&["alias", "add", "fl", "https://github.com/pallets/flask"])?.success();
+    run(&["alias", "list"])?
+        .success()
+        .stdout(predicate::str::contains("fl  /pallets/flask"));
+    run(&["get-docs", "fl"])?
+        .success()
+        .stdout(predicate::str::contains("# Flask docs"));
+    run(&["alias", "remove", "fl"])?.success();
+    run(&["get-docs", "fl"])?.failure();
+
+    std::fs::remove_dir_all(base)?;
+    Ok(())
+}
+
+/// Test that the shell keeps the last results so `docs <n>` can fetch one of them
+#[test]
+fn test_shell_search_then_docs() -> Result<(), Box<dyn std::error::Error>> {
+    let base = std::env::temp_dir().join(format!("context7-cli-shell-{}", std::process::id()));
+    let cache_dir = base.join("cache");
+    std::fs::create_dir_all(cache_dir.join("search"))?;
+    std::fs::create_dir_all(cache_dir.join("docs").join("pallets"))?;
+    std::fs::write(
+        cache_dir.join("search").join("flask-p1.json"),
+        r#"{"results":[{"id":"/pallets/flask","title":"Flask","stars":65000},{"id":"/small/flask-extras","title":"Flask Extras","stars":10}]}"#,
+    )?;
+    std::fs::write(
+        cache_dir.join("docs").join("pallets").join("flask.txt"),
+        "# Flask docs",
+    )?;
+
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.env("CONTEXT7_OFFLINE", "1")
+        .env("CONTEXT7_CACHE_DIR", &cache_dir)
+        .env("XDG_CONFIG_HOME", base.join("config"))
+        .env("XDG_DATA_HOME", base.join("data"))
+        .env_remove("CONTEXT7_CONFIG")
+        .env_remove("CONTEXT7_PROFILE")
+        .arg("shell")
+        .write_stdin("search flask\nlimit 1\ndocs 1\nfetch\n");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("1. /pallets/flask  Flask"))
+        .stdout(predicate::str::contains("2. /small/flask-extras"))
+        .stdout(predicate::str::contains("# Flask docs"))
+        .stderr(predicate::str::contains("Unknown command 'fetch'"));
+
+    std::fs::remove_dir_all(base)?;
+    Ok(())
+}
+
+/// Test that the completions command prints a script for the requested shell
+#[test]
+fn test_completions_command() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.args(["completions", "bash"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("COMPLETE=\"bash\""));
+
+    let mut cmd = Command::cargo_bin("context7-cli")?;
+    cmd.args(["completions", "zsh", "--static"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("#compdef context7-cli"));
+
+    Ok(())
+}
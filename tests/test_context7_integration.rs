@@ -5,6 +5,7 @@
 ///
 /// Run with: RUN_NETWORK_TESTS=1 cargo test -- --ignored
 use context7_cli::clients::Context7ClientTrait;
+use context7_cli::models::LibraryId;
 
 /// Helper function to check if network tests should run
 fn should_run_network_tests() -> bool {
@@ -47,7 +48,7 @@ async fn test_context7_get_docs_returns_content() {
 
     let client = Context7Client::new();
     let docs = client
-        .get_docs("/fastapi/fastapi")
+        .get_docs(&LibraryId::parse("/fastapi/fastapi").unwrap())
         .await
         .expect("Get docs should succeed");
 
@@ -71,7 +72,7 @@ async fn test_context7_get_docs_with_leading_slash() {
 
     let client = Context7Client::new();
     let docs = client
-        .get_docs("/fastapi/fastapi")
+        .get_docs(&LibraryId::parse("/fastapi/fastapi").unwrap())
         .await
         .expect("Should handle leading slash");
 
@@ -90,7 +91,7 @@ async fn test_context7_get_docs_without_leading_slash() {
 
     let client = Context7Client::new();
     let docs = client
-        .get_docs("fastapi/fastapi")
+        .get_docs(&LibraryId::parse("fastapi/fastapi").unwrap())
         .await
         .expect("Should handle ID without leading slash");
 